@@ -0,0 +1,573 @@
+//! Season-long Elo ratings for teams.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+};
+
+use crate::schema::{Alliance, AllianceColor, EventId, IdInfo, Match, MatchId, MatchRound, TeamId};
+
+/// Incrementally updated Elo ratings for every team in a set of matches.
+///
+/// Each scored match is treated as a game between its red and blue alliances, with each
+/// alliance rated as the average of its teams' ratings. Every team on an alliance gains or
+/// loses the same number of points, depending on how the result compares to what the alliances'
+/// ratings predicted. Practice matches, unscored matches, matches without both a red and a blue
+/// alliance and teams sitting out a match are ignored.
+///
+/// Matches can be fed in over time with [`Self::update`], such as after each event of a
+/// season. Matches that have already been rated are skipped, so the same list can be passed
+/// again as it grows.
+///
+/// # Examples
+///
+/// Rating teams across a season, one event at a time:
+///
+/// ```
+/// use robotevents::analysis::EloRatings;
+/// use robotevents::schema::Match;
+///
+/// fn print_leaderboard(events: &[Vec<Match>]) {
+///     let mut ratings = EloRatings::new();
+///     for matches in events {
+///         ratings.update(matches);
+///     }
+///
+///     for (i, team) in ratings.leaderboard().iter().enumerate().take(10) {
+///         println!("{}. {} ({:.0})", i + 1, team.team.name, team.rating);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EloRatings {
+    initial_rating: f64,
+    k_factor: f64,
+    teams: BTreeMap<TeamId, TeamRating>,
+    rated: HashSet<MatchId>,
+}
+
+impl Default for EloRatings {
+    fn default() -> Self {
+        Self {
+            initial_rating: 1500.0,
+            k_factor: 32.0,
+            teams: BTreeMap::new(),
+            rated: HashSet::new(),
+        }
+    }
+}
+
+impl EloRatings {
+    /// Creates an empty set of ratings, with an initial rating of 1500 and a K-factor of 32.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the rating teams start with before their first match.
+    pub fn initial_rating(mut self, initial_rating: f64) -> Self {
+        self.initial_rating = initial_rating;
+        self
+    }
+
+    /// Sets the K-factor, the most points a team can gain or lose in a single match.
+    pub fn k_factor(mut self, k_factor: f64) -> Self {
+        self.k_factor = k_factor;
+        self
+    }
+
+    /// Rates any matches that haven't been rated yet, returning how many were rated.
+    ///
+    /// New matches are rated in chronological order, using the time each match started or
+    /// else its scheduled time. Matches without either are rated after the others, in the
+    /// order they were played within their event. Matches should be fed in roughly in the
+    /// order they were played, since ones older than previously rated matches are still rated
+    /// after them.
+    ///
+    /// Timestamps are compared as points in time, so matches played in different time zones
+    /// are rated in the order they actually happened. Timestamps that aren't valid RFC 3339 are
+    /// treated as missing.
+    pub fn update(&mut self, matches: &[Match]) -> usize {
+        let mut pending: Vec<&Match> = matches
+            .iter()
+            .filter(|m| m.scored && m.round != MatchRound::Practice)
+            .filter(|m| !self.rated.contains(&m.id))
+            .collect();
+        pending.sort_by(|a, b| chronological(a, b));
+
+        pending.into_iter().filter(|m| self.rate(m)).count()
+    }
+
+    /// A team's current rating, if it has played in a rated match.
+    pub fn team(&self, team_id: TeamId) -> Option<&TeamRating> {
+        self.teams.get(&team_id)
+    }
+
+    /// Every rated team, from highest to lowest rating.
+    pub fn leaderboard(&self) -> Vec<&TeamRating> {
+        let mut teams: Vec<_> = self.teams.values().collect();
+        teams.sort_by(|a, b| {
+            b.rating
+                .total_cmp(&a.rating)
+                .then(a.team.id.cmp(&b.team.id))
+        });
+        teams
+    }
+
+    /// Updates the ratings of the teams in a match, returning whether it could be rated.
+    fn rate(&mut self, m: &Match) -> bool {
+        let (Some(red), Some(blue)) = (m.red(), m.blue()) else {
+            return false;
+        };
+        let (Some(red_rating), Some(blue_rating)) =
+            (self.alliance_rating(red), self.alliance_rating(blue))
+        else {
+            return false;
+        };
+        if !self.rated.insert(m.id) {
+            return false;
+        }
+
+        let expected = 1.0 / (1.0 + 10f64.powf((blue_rating - red_rating) / 400.0));
+        let actual = match m.winner() {
+            Some(AllianceColor::Red) => 1.0,
+            Some(AllianceColor::Blue) => 0.0,
+            _ => 0.5,
+        };
+        let change = self.k_factor * (actual - expected);
+
+        for (alliance, change) in [(red, change), (blue, -change)] {
+            for team in alliance.teams.iter().filter(|team| !team.sitting) {
+                let rating = self
+                    .teams
+                    .entry(team.team.id)
+                    .or_insert_with(|| TeamRating {
+                        team: team.team.clone(),
+                        rating: self.initial_rating,
+                        history: Vec::new(),
+                    });
+
+                let before = rating.rating;
+                rating.rating += change;
+                rating.history.push(RatingChange {
+                    match_id: m.id,
+                    event: m.event.id,
+                    played: played_at(m).cloned(),
+                    before,
+                    after: rating.rating,
+                });
+            }
+        }
+
+        true
+    }
+
+    /// The average rating of the teams playing on an alliance, if any are.
+    fn alliance_rating(&self, alliance: &Alliance) -> Option<f64> {
+        let ratings: Vec<f64> = alliance
+            .teams
+            .iter()
+            .filter(|team| !team.sitting)
+            .map(|team| {
+                self.teams
+                    .get(&team.team.id)
+                    .map_or(self.initial_rating, |rating| rating.rating)
+            })
+            .collect();
+
+        if ratings.is_empty() {
+            None
+        } else {
+            Some(ratings.iter().sum::<f64>() / ratings.len() as f64)
+        }
+    }
+}
+
+/// When a match started, or else when it was scheduled.
+fn played_at(m: &Match) -> Option<&String> {
+    m.started.as_ref().or(m.scheduled.as_ref())
+}
+
+/// When a match was played, as a point in time.
+fn played_key(m: &Match) -> Option<(i64, u32)> {
+    let started = m.started.as_deref().and_then(utc_instant);
+    started.or_else(|| m.scheduled.as_deref().and_then(utc_instant))
+}
+
+/// Converts an RFC 3339 timestamp such as `2024-10-05T09:00:00-05:00` into seconds and
+/// nanoseconds since the Unix epoch, so that timestamps with different UTC offsets can be
+/// compared without the `chrono` feature.
+fn utc_instant(timestamp: &str) -> Option<(i64, u32)> {
+    let number = |digits: &str| -> Option<i64> {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+
+    let (date, time) = timestamp.split_once(['T', 't', ' '])?;
+    let mut date = date.splitn(3, '-');
+    let year = number(date.next()?)?;
+    let month = number(date.next()?)?;
+    let day = number(date.next()?)?;
+
+    let (clock, offset) = time.split_at(time.find(['Z', 'z', '+', '-'])?);
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut clock = clock.splitn(3, ':');
+    let hour = number(clock.next()?)?;
+    let minute = number(clock.next()?)?;
+    let second = number(clock.next()?)?;
+
+    let offset = match offset {
+        "Z" | "z" => 0,
+        _ => {
+            let (sign, offset) = offset.split_at(1);
+            let (hours, minutes) = offset.split_once(':')?;
+            let seconds = number(hours)? * 3600 + number(minutes)? * 60;
+            if sign == "-" {
+                -seconds
+            } else {
+                seconds
+            }
+        }
+    };
+
+    let nanos = match fraction {
+        "" => 0,
+        fraction => {
+            number(fraction)?;
+            let digits: String = fraction
+                .chars()
+                .chain(std::iter::repeat('0'))
+                .take(9)
+                .collect();
+            digits.parse().ok()?
+        }
+    };
+
+    let valid = (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && hour < 24
+        && minute < 60
+        && second <= 60;
+    if !valid {
+        return None;
+    }
+
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+    Some((seconds - offset, nanos))
+}
+
+/// The number of days between 1970-01-01 and a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Count years from March, so that leap days fall at the end of the year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Orders matches by when they were played.
+fn chronological(a: &Match, b: &Match) -> Ordering {
+    let stage = |m: &Match| match m.round {
+        MatchRound::Practice => 0,
+        MatchRound::Qualification => 1,
+        MatchRound::RoundOf16 => 2,
+        MatchRound::Quarterfinals => 3,
+        MatchRound::Semifinals => 4,
+        MatchRound::Finals => 5,
+        MatchRound::Unknown(_) => 6,
+    };
+
+    let (a_time, b_time) = (played_key(a), played_key(b));
+    a_time
+        .is_none()
+        .cmp(&b_time.is_none())
+        .then_with(|| a_time.cmp(&b_time))
+        .then(a.event.id.cmp(&b.event.id))
+        .then(stage(a).cmp(&stage(b)))
+        .then(a.instance.cmp(&b.instance))
+        .then(a.matchnum.cmp(&b.matchnum))
+}
+
+/// A team's Elo rating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamRating {
+    pub team: IdInfo<TeamId>,
+    pub rating: f64,
+
+    /// Every change to the team's rating, from oldest to newest.
+    pub history: Vec<RatingChange>,
+}
+
+/// A change to a team's rating from a single match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingChange {
+    pub match_id: MatchId,
+    pub event: EventId,
+
+    /// When the match started, or else when it was scheduled.
+    pub played: Option<String>,
+
+    pub before: f64,
+    pub after: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::matches::tests::MatchBuilder;
+
+    /// A qualification match between teams 1 and 2 on red and teams 3 and 4 on blue.
+    fn game(id: i32, started: &str, red: i32, blue: i32) -> Match {
+        MatchBuilder::qualification(id)
+            .id(id)
+            .started(started)
+            .red(&[1, 2], red)
+            .blue(&[3, 4], blue)
+            .build()
+    }
+
+    fn rounded(rating: f64) -> f64 {
+        (rating * 100.0).round() / 100.0
+    }
+
+    fn history(ratings: &EloRatings, team: i32) -> Vec<i32> {
+        ratings
+            .team(TeamId(team))
+            .unwrap()
+            .history
+            .iter()
+            .map(|change| change.match_id.0)
+            .collect()
+    }
+
+    #[test]
+    fn even_match() {
+        let mut ratings = EloRatings::new();
+        assert_eq!(
+            ratings.update(&[game(1, "2024-10-05T09:00:00-05:00", 50, 40)]),
+            1
+        );
+
+        let leaderboard: Vec<_> = ratings
+            .leaderboard()
+            .iter()
+            .map(|team| (team.team.id.0, team.rating))
+            .collect();
+        assert_eq!(
+            leaderboard,
+            [(1, 1516.0), (2, 1516.0), (3, 1484.0), (4, 1484.0)]
+        );
+    }
+
+    #[test]
+    fn ties_between_even_alliances_change_nothing() {
+        let mut ratings = EloRatings::new();
+        ratings.update(&[game(1, "2024-10-05T09:00:00-05:00", 40, 40)]);
+
+        assert!(ratings
+            .leaderboard()
+            .iter()
+            .all(|team| team.rating == 1500.0));
+    }
+
+    #[test]
+    fn upsets_are_worth_more() {
+        let mut ratings = EloRatings::new();
+        ratings.update(&[
+            game(1, "2024-10-05T09:00:00-05:00", 50, 40),
+            game(2, "2024-10-05T09:10:00-05:00", 20, 30),
+        ]);
+
+        let team_3 = ratings.team(TeamId(3)).unwrap();
+        assert_eq!(rounded(team_3.rating), 1501.47);
+
+        let changes: Vec<_> = team_3
+            .history
+            .iter()
+            .map(|change| {
+                (
+                    change.match_id.0,
+                    rounded(change.before),
+                    rounded(change.after),
+                )
+            })
+            .collect();
+        assert_eq!(changes, [(1, 1500.0, 1484.0), (2, 1484.0, 1501.47)]);
+        assert_eq!(
+            team_3.history[1].played.as_deref(),
+            Some("2024-10-05T09:10:00-05:00")
+        );
+    }
+
+    #[test]
+    fn settings() {
+        let mut ratings = EloRatings::new().initial_rating(1000.0).k_factor(10.0);
+        ratings.update(&[game(1, "2024-10-05T09:00:00-05:00", 50, 40)]);
+
+        assert_eq!(ratings.team(TeamId(1)).unwrap().rating, 1005.0);
+        assert_eq!(ratings.team(TeamId(3)).unwrap().rating, 995.0);
+    }
+
+    #[test]
+    fn rates_matches_chronologically() {
+        let mut ratings = EloRatings::new();
+        let untimed = MatchBuilder::qualification(3)
+            .id(3)
+            .red(&[1, 2], 50)
+            .blue(&[3, 4], 40)
+            .build();
+
+        ratings.update(&[
+            untimed,
+            game(2, "2024-10-05T09:10:00-05:00", 20, 30),
+            game(1, "2024-10-05T09:00:00-05:00", 50, 40),
+        ]);
+
+        // Matches without a time are rated last.
+        assert_eq!(history(&ratings, 3), [1, 2, 3]);
+        let team_3 = ratings.team(TeamId(3)).unwrap();
+        assert_eq!(rounded(team_3.history[1].after), 1501.47);
+    }
+
+    #[test]
+    fn compares_timestamps_across_offsets() {
+        let mut ratings = EloRatings::new();
+        // 14:30 UTC, then 14:00 UTC. As strings, the first would sort first.
+        ratings.update(&[
+            game(1, "2024-10-05T09:30:00-05:00", 50, 40),
+            game(2, "2024-10-05T10:00:00-04:00", 50, 40),
+        ]);
+
+        assert_eq!(history(&ratings, 1), [2, 1]);
+    }
+
+    #[test]
+    fn timestamps_to_utc() {
+        let instants = [
+            ("1970-01-01T00:00:00Z", (0, 0)),
+            ("1969-12-31T23:59:59+00:00", (-1, 0)),
+            ("2000-02-29T00:00:00.5+00:00", (951_782_400, 500_000_000)),
+            ("2024-10-05T09:00:00-05:00", (1_728_136_800, 0)),
+            ("2024-10-05T14:00:00.000000Z", (1_728_136_800, 0)),
+            ("2025-01-01T00:30:00+05:30", (1_735_671_600, 0)),
+        ];
+        for (timestamp, instant) in instants {
+            assert_eq!(utc_instant(timestamp), Some(instant), "{timestamp}");
+        }
+
+        for invalid in [
+            "",
+            "April 25th",
+            "2024-10-05",
+            "2024-10-05T09:00:00",
+            "2024-13-05T09:00:00Z",
+            "2024-10-05T25:00:00Z",
+            "2024-10-05T09:00:00+0500",
+            "2024-10-05T09:00:00.x1Z",
+        ] {
+            assert_eq!(utc_instant(invalid), None, "{invalid}");
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamps_agree_with_chrono() {
+        for timestamp in [
+            "2024-10-05T09:00:00-05:00",
+            "1999-12-31T23:59:59.999+13:45",
+            "2100-03-01T00:00:00Z",
+            "1900-02-28T12:00:00-00:30",
+        ] {
+            let parsed = chrono::DateTime::parse_from_rfc3339(timestamp).unwrap();
+            assert_eq!(
+                utc_instant(timestamp),
+                Some((parsed.timestamp(), parsed.timestamp_subsec_nanos())),
+                "{timestamp}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_timestamps_are_rated_last() {
+        let mut ratings = EloRatings::new();
+        ratings.update(&[
+            game(1, "sometime", 50, 40),
+            game(2, "2024-10-05T09:00:00-05:00", 50, 40),
+        ]);
+
+        assert_eq!(history(&ratings, 1), [2, 1]);
+    }
+
+    #[test]
+    fn skips_rated_matches() {
+        let mut ratings = EloRatings::new();
+        let mut matches = vec![game(1, "2024-10-05T09:00:00-05:00", 50, 40)];
+        assert_eq!(ratings.update(&matches), 1);
+
+        // Only the new match is rated when the list is fed in again.
+        matches.push(game(2, "2024-10-05T09:10:00-05:00", 20, 30));
+        assert_eq!(ratings.update(&matches), 1);
+        assert_eq!(ratings.update(&matches), 0);
+
+        assert_eq!(history(&ratings, 1), [1, 2]);
+    }
+
+    #[test]
+    fn skips_duplicate_matches_in_a_batch() {
+        let mut ratings = EloRatings::new();
+        let m = game(1, "2024-10-05T09:00:00-05:00", 50, 40);
+
+        assert_eq!(ratings.update(&[m.clone(), m]), 1);
+        assert_eq!(ratings.team(TeamId(1)).unwrap().rating, 1516.0);
+    }
+
+    #[test]
+    fn ignores_unratable_matches() {
+        let mut ratings = EloRatings::new();
+        let practice = MatchBuilder::new(MatchRound::Practice, 1, 1)
+            .id(1)
+            .red(&[1, 2], 50)
+            .blue(&[3, 4], 40)
+            .build();
+        let unscored = MatchBuilder::qualification(2)
+            .id(2)
+            .red(&[1, 2], 0)
+            .blue(&[3, 4], 0)
+            .unscored()
+            .build();
+        let red_only = MatchBuilder::qualification(3)
+            .id(3)
+            .red(&[1, 2], 50)
+            .build();
+        let all_sitting = MatchBuilder::qualification(4)
+            .id(4)
+            .red(&[1, 2], 50)
+            .blue(&[3], 40)
+            .sitting(3)
+            .build();
+
+        assert_eq!(
+            ratings.update(&[practice, unscored, red_only, all_sitting]),
+            0
+        );
+        assert!(ratings.leaderboard().is_empty());
+    }
+
+    #[test]
+    fn sitting_teams_keep_their_rating() {
+        let mut ratings = EloRatings::new();
+        let m = MatchBuilder::qualification(1)
+            .id(1)
+            .red(&[1, 2, 5], 50)
+            .blue(&[3, 4], 40)
+            .sitting(5)
+            .build();
+
+        assert_eq!(ratings.update(&[m]), 1);
+        assert_eq!(ratings.team(TeamId(5)), None);
+        assert_eq!(ratings.team(TeamId(1)).unwrap().rating, 1516.0);
+    }
+}
@@ -0,0 +1,12 @@
+//! Statistics computed from RobotEvents data.
+//!
+//! These work on the [`schema`](crate::schema) types returned by the client, so they can be
+//! used with data fetched from the API, loaded from a cassette or built by hand.
+
+pub mod elo;
+pub mod power;
+pub mod rankings;
+
+pub use elo::*;
+pub use power::*;
+pub use rankings::*;
@@ -0,0 +1,322 @@
+//! Offensive and defensive power ratings (OPR, DPR and CCWM).
+
+use std::collections::BTreeMap;
+
+use crate::schema::{IdInfo, Match, MatchRound, TeamId};
+
+/// Pivots smaller than this are treated as zero when solving for ratings.
+const EPSILON: f64 = 1e-9;
+
+/// A team's power ratings, estimated from the matches it played in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerRating {
+    pub team: IdInfo<TeamId>,
+
+    /// Offensive power rating: the team's estimated contribution to its alliance's score.
+    pub opr: f64,
+
+    /// Defensive power rating: the team's estimated contribution to its opponents' score.
+    /// Lower is better.
+    pub dpr: f64,
+
+    /// Calculated contribution to winning margin, equal to `opr - dpr`.
+    pub ccwm: f64,
+
+    /// The number of matches the ratings are based on.
+    pub matches: usize,
+}
+
+/// Computes the power ratings of every team in a list of matches, such as those returned by
+/// [`RobotEvents::event_division_matches`](crate::RobotEvents::event_division_matches).
+///
+/// Each alliance's score is modeled as the sum of its teams' OPRs, and its opponents' score as
+/// the sum of its teams' DPRs. Both are solved for with least squares. Unscored matches,
+/// practice matches and matches without both a red and a blue alliance are ignored, as are
+/// teams sitting out a match.
+///
+/// Ratings are returned in order of team ID.
+///
+/// # Errors
+///
+/// Returns [`PowerRatingsError::NoMatches`] if none of the matches can be used, and
+/// [`PowerRatingsError::Underdetermined`] if they don't determine every team's ratings, such as
+/// early in an event when there are fewer matches than teams.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::analysis::power_ratings;
+/// use robotevents::schema::Match;
+///
+/// fn print_top_teams(matches: &[Match]) {
+///     let Ok(mut ratings) = power_ratings(matches) else {
+///         return;
+///     };
+///     ratings.sort_by(|a, b| b.opr.total_cmp(&a.opr));
+///
+///     for rating in ratings.iter().take(5) {
+///         println!("{}: OPR {:.1}, CCWM {:.1}", rating.team.name, rating.opr, rating.ccwm);
+///     }
+/// }
+/// ```
+pub fn power_ratings(matches: &[Match]) -> Result<Vec<PowerRating>, PowerRatingsError> {
+    let mut teams: BTreeMap<TeamId, (IdInfo<TeamId>, usize)> = BTreeMap::new();
+    let mut rows = Vec::new();
+
+    let counted = matches
+        .iter()
+        .filter(|m| m.scored && m.round != MatchRound::Practice);
+
+    for m in counted {
+        let (Some(red), Some(blue)) = (m.red(), m.blue()) else {
+            continue;
+        };
+
+        for (alliance, opponent) in [(red, blue), (blue, red)] {
+            let playing: Vec<TeamId> = alliance
+                .teams
+                .iter()
+                .filter(|team| !team.sitting)
+                .map(|team| {
+                    teams
+                        .entry(team.team.id)
+                        .or_insert_with(|| (team.team.clone(), 0))
+                        .1 += 1;
+                    team.team.id
+                })
+                .collect();
+
+            if !playing.is_empty() {
+                rows.push((playing, alliance.score, opponent.score));
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(PowerRatingsError::NoMatches);
+    }
+
+    let index: BTreeMap<TeamId, usize> = teams.keys().enumerate().map(|(i, id)| (*id, i)).collect();
+    let n = teams.len();
+
+    // Normal equations (AᵀA x = Aᵀb), augmented with the alliance and opponent scores.
+    let mut system = vec![vec![0.0; n + 2]; n];
+    for (playing, score, opponent_score) in &rows {
+        for team in playing {
+            let row = &mut system[index[team]];
+            for partner in playing {
+                row[index[partner]] += 1.0;
+            }
+            row[n] += f64::from(*score);
+            row[n + 1] += f64::from(*opponent_score);
+        }
+    }
+
+    let solution = solve(system).ok_or(PowerRatingsError::Underdetermined)?;
+
+    Ok(teams
+        .into_values()
+        .zip(solution)
+        .map(|((team, matches), [opr, dpr])| PowerRating {
+            team,
+            opr,
+            dpr,
+            ccwm: opr - dpr,
+            matches,
+        })
+        .collect())
+}
+
+/// Solves an augmented `n × (n + 2)` linear system for both right-hand sides using Gauss-Jordan
+/// elimination with partial pivoting, returning `None` if it's singular.
+fn solve(mut system: Vec<Vec<f64>>) -> Option<Vec<[f64; 2]>> {
+    let n = system.len();
+
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))?;
+        if system[pivot][col].abs() < EPSILON {
+            return None;
+        }
+        system.swap(col, pivot);
+
+        let pivot_row = system[col].clone();
+        for (i, row) in system.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            if i == col || factor == 0.0 {
+                continue;
+            }
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+
+    Some(
+        system
+            .iter()
+            .enumerate()
+            .map(|(i, row)| [row[n] / row[i], row[n + 1] / row[i]])
+            .collect(),
+    )
+}
+
+/// An error returned by [`power_ratings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerRatingsError {
+    /// None of the matches were scored, non-practice matches between a red and a blue alliance.
+    NoMatches,
+
+    /// The matches don't determine every team's ratings, such as when there are fewer matches
+    /// than teams or two teams always played on the same alliance.
+    Underdetermined,
+}
+
+impl std::fmt::Display for PowerRatingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NoMatches => "no scored matches to compute power ratings from",
+            Self::Underdetermined => "not enough matches to determine every team's power ratings",
+        })
+    }
+}
+
+impl std::error::Error for PowerRatingsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::matches::tests::MatchBuilder;
+
+    /// Every pair of teams 1-4 plays together once, scoring 10 points for team 1, 20 for
+    /// team 2, 30 for team 3 and 40 for team 4.
+    fn round_robin() -> Vec<Match> {
+        vec![
+            MatchBuilder::qualification(1)
+                .red(&[1, 2], 30)
+                .blue(&[3, 4], 70)
+                .build(),
+            MatchBuilder::qualification(2)
+                .red(&[1, 3], 40)
+                .blue(&[2, 4], 60)
+                .build(),
+            MatchBuilder::qualification(3)
+                .red(&[1, 4], 50)
+                .blue(&[2, 3], 50)
+                .build(),
+        ]
+    }
+
+    fn rounded(ratings: &[PowerRating], field: fn(&PowerRating) -> f64) -> Vec<f64> {
+        ratings
+            .iter()
+            .map(|rating| (field(rating) * 1000.0).round() / 1000.0)
+            .collect()
+    }
+
+    fn team_ids(ratings: &[PowerRating]) -> Vec<i32> {
+        ratings.iter().map(|rating| rating.team.id.0).collect()
+    }
+
+    #[test]
+    fn solves_a_round_robin() {
+        let ratings = power_ratings(&round_robin()).unwrap();
+
+        assert_eq!(team_ids(&ratings), [1, 2, 3, 4]);
+        assert_eq!(rounded(&ratings, |r| r.opr), [10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(rounded(&ratings, |r| r.dpr), [40.0, 30.0, 20.0, 10.0]);
+        assert_eq!(rounded(&ratings, |r| r.ccwm), [-30.0, -10.0, 10.0, 30.0]);
+        assert!(ratings.iter().all(|rating| rating.matches == 3));
+    }
+
+    #[test]
+    fn too_few_matches_are_underdetermined() {
+        assert_eq!(
+            power_ratings(&round_robin()[..2]),
+            Err(PowerRatingsError::Underdetermined)
+        );
+    }
+
+    #[test]
+    fn ignores_practice_unscored_and_single_alliance_matches() {
+        let mut matches = round_robin();
+        matches.push(
+            MatchBuilder::new(MatchRound::Practice, 1, 1)
+                .red(&[1, 2], 999)
+                .blue(&[3, 4], 0)
+                .build(),
+        );
+        matches.push(
+            MatchBuilder::qualification(4)
+                .red(&[1, 2], 999)
+                .blue(&[3, 4], 0)
+                .unscored()
+                .build(),
+        );
+        matches.push(MatchBuilder::qualification(5).red(&[1, 5], 999).build());
+
+        let ratings = power_ratings(&matches).unwrap();
+
+        // Team 5 only played in the single-alliance match.
+        assert_eq!(team_ids(&ratings), [1, 2, 3, 4]);
+        assert_eq!(rounded(&ratings, |r| r.opr), [10.0, 20.0, 30.0, 40.0]);
+        assert!(ratings.iter().all(|rating| rating.matches == 3));
+    }
+
+    #[test]
+    fn counts_elimination_matches() {
+        let mut matches = round_robin();
+        matches.push(
+            MatchBuilder::new(MatchRound::Finals, 1, 1)
+                .red(&[1, 4], 50)
+                .blue(&[2, 3], 50)
+                .build(),
+        );
+
+        let ratings = power_ratings(&matches).unwrap();
+
+        assert_eq!(rounded(&ratings, |r| r.opr), [10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(
+            ratings.iter().map(|r| r.matches).collect::<Vec<_>>(),
+            [4, 4, 4, 4]
+        );
+    }
+
+    #[test]
+    fn excludes_sitting_teams() {
+        let mut matches = round_robin();
+        // Team 5 sits out every match it's listed in, so it gets no rating and doesn't
+        // affect its partners'.
+        matches[0] = MatchBuilder::qualification(1)
+            .red(&[1, 2, 5], 30)
+            .blue(&[3, 4], 70)
+            .sitting(5)
+            .build();
+
+        let ratings = power_ratings(&matches).unwrap();
+
+        assert_eq!(team_ids(&ratings), [1, 2, 3, 4]);
+        assert_eq!(rounded(&ratings, |r| r.opr), [10.0, 20.0, 30.0, 40.0]);
+    }
+
+    #[test]
+    fn no_usable_matches() {
+        assert_eq!(power_ratings(&[]), Err(PowerRatingsError::NoMatches));
+
+        let practice = MatchBuilder::new(MatchRound::Practice, 1, 1)
+            .red(&[1, 2], 30)
+            .blue(&[3, 4], 70)
+            .build();
+        let unscored = MatchBuilder::qualification(1)
+            .red(&[1, 2], 0)
+            .blue(&[3, 4], 0)
+            .unscored()
+            .build();
+        let red_only = MatchBuilder::qualification(2).red(&[1, 2], 30).build();
+
+        assert_eq!(
+            power_ratings(&[practice, unscored, red_only]),
+            Err(PowerRatingsError::NoMatches)
+        );
+    }
+}
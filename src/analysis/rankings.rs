@@ -0,0 +1,462 @@
+//! Qualification rankings recomputed from match results.
+
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use crate::schema::{
+    Alliance, DivisionId, EventId, IdInfo, Match, MatchRound, ProgramId, Ranking, TeamId,
+};
+
+/// Computes a division's qualification rankings from its matches, following a program's
+/// ranking rules.
+///
+/// This can be used to cross-check the rankings returned by
+/// [`RobotEvents::event_division_rankings`](crate::RobotEvents::event_division_rankings), or to
+/// forecast them by adding hypothetical results for matches that haven't been played yet.
+///
+/// Autonomous points (AP) and Autonomous Win Points (AWP) aren't part of match results, so
+/// they have to be provided with [`Self::autonomous_points`] and
+/// [`Self::autonomous_win_points`] or carried over from existing rankings with
+/// [`Self::autonomous_points_from`]. Teams without any default to zero.
+///
+/// # Ranking rules
+///
+/// In V5RC, VEXU and other head-to-head programs, a win is worth two win points (WP), a tie one
+/// and each AWP one more, and every match earns both alliances strength of schedule points (SP)
+/// equal to the losing alliance's score. Teams are ranked by WP, then AP, then SP, then their highest match
+/// score, second highest match score and so on.
+///
+/// In VIQRC, both teams on the alliance share its score and teams are ranked by their average
+/// score, then their highest match score, second highest and so on. Dropping each team's lowest
+/// scores isn't modeled.
+///
+/// Teams still tied after every tiebreaker (which RobotEvents settles with a random draw) are
+/// ordered by team ID so the result is deterministic.
+///
+/// # Examples
+///
+/// Checking a division's rankings against its match results:
+///
+/// ```
+/// use robotevents::analysis::RankingCalculator;
+/// use robotevents::schema::{Match, ProgramId, Ranking};
+///
+/// fn rankings_match(matches: &[Match], rankings: &[Ranking]) -> bool {
+///     let computed = RankingCalculator::new(ProgramId::V5RC)
+///         .autonomous_points_from(rankings)
+///         .rank(matches);
+///
+///     computed
+///         .iter()
+///         .zip(rankings)
+///         .all(|(a, b)| a.rank == b.rank && a.team.id == b.team.id)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingCalculator {
+    program: ProgramId,
+    autonomous_points: BTreeMap<TeamId, i32>,
+    autonomous_win_points: BTreeMap<TeamId, i32>,
+}
+
+impl RankingCalculator {
+    /// Creates a calculator using the ranking rules of `program`.
+    pub fn new(program: ProgramId) -> Self {
+        Self {
+            program,
+            autonomous_points: BTreeMap::new(),
+            autonomous_win_points: BTreeMap::new(),
+        }
+    }
+
+    /// Sets a team's autonomous points.
+    pub fn autonomous_points(mut self, team: TeamId, ap: i32) -> Self {
+        self.autonomous_points.insert(team, ap);
+        self
+    }
+
+    /// Sets the number of Autonomous Win Points a team has earned, each worth one WP.
+    pub fn autonomous_win_points(mut self, team: TeamId, awp: i32) -> Self {
+        self.autonomous_win_points.insert(team, awp);
+        self
+    }
+
+    /// Sets the autonomous points and Autonomous Win Points of every team in existing rankings,
+    /// such as those returned by
+    /// [`RobotEvents::event_division_rankings`](crate::RobotEvents::event_division_rankings).
+    ///
+    /// Rankings don't list AWPs separately, so a team's AWPs are taken to be the WP it has
+    /// beyond those for its wins and ties.
+    pub fn autonomous_points_from(mut self, rankings: &[Ranking]) -> Self {
+        for ranking in rankings {
+            let awp = ranking.wp - 2 * ranking.wins - ranking.ties;
+            self.autonomous_points.insert(ranking.team.id, ranking.ap);
+            self.autonomous_win_points
+                .insert(ranking.team.id, awp.max(0));
+        }
+        self
+    }
+
+    /// Ranks every team that played in the scored qualification matches of `matches`.
+    ///
+    /// Rankings are per division, so only matches from the same event and division as the
+    /// first scored qualification match are ranked. Matches from any other division are
+    /// ignored.
+    ///
+    /// The rankings have an `id` of `0`, since they don't come from RobotEvents.
+    pub fn rank(&self, matches: &[Match]) -> Vec<Ranking> {
+        let mut records: BTreeMap<TeamId, Record> = BTreeMap::new();
+        let mut event: Option<(IdInfo<EventId>, IdInfo<DivisionId>)> = None;
+
+        let qualifications = matches
+            .iter()
+            .filter(|m| m.scored && m.round == MatchRound::Qualification);
+
+        for m in qualifications {
+            let (event, division) =
+                event.get_or_insert_with(|| (m.event.clone(), m.division.clone()));
+            if m.event.id != event.id || m.division.id != division.id {
+                continue;
+            }
+
+            if self.program == ProgramId::VIQRC {
+                for alliance in &m.alliances {
+                    Record::update_playing(&mut records, alliance, |record| {
+                        record.scores.push(alliance.score);
+                    });
+                }
+                continue;
+            }
+
+            let (Some(red), Some(blue)) = (m.red(), m.blue()) else {
+                continue;
+            };
+            let winner = m.winner();
+            let sp = red.score.min(blue.score);
+
+            for alliance in [red, blue] {
+                Record::update_playing(&mut records, alliance, |record| {
+                    match &winner {
+                        None => {
+                            record.ties += 1;
+                            record.wp += 1;
+                        }
+                        Some(color) if *color == alliance.color => {
+                            record.wins += 1;
+                            record.wp += 2;
+                        }
+                        Some(_) => record.losses += 1,
+                    }
+                    record.sp += sp;
+                    record.scores.push(alliance.score);
+                });
+            }
+        }
+
+        let Some((event, division)) = event else {
+            return Vec::new();
+        };
+
+        for (team, record) in &mut records {
+            record.ap = self.autonomous_points.get(team).copied().unwrap_or(0);
+            if self.program != ProgramId::VIQRC {
+                record.wp += self.autonomous_win_points.get(team).copied().unwrap_or(0);
+            }
+            record.scores.sort_unstable_by(|a, b| b.cmp(a));
+        }
+
+        // Records are already in order of team ID, which the stable sort keeps for full ties.
+        let mut records: Vec<_> = records.into_values().collect();
+        records.sort_by(|a, b| self.compare(a, b));
+
+        records
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let total_points: i32 = record.scores.iter().sum();
+                let average_points = record.average();
+                Ranking {
+                    id: 0,
+                    event: event.clone(),
+                    division: division.clone(),
+                    rank: i as i32 + 1,
+                    team: record.team,
+                    wins: record.wins,
+                    losses: record.losses,
+                    ties: record.ties,
+                    wp: record.wp,
+                    ap: record.ap,
+                    sp: record.sp,
+                    high_score: record.scores.first().copied().unwrap_or(0),
+                    average_points,
+                    total_points,
+                }
+            })
+            .collect()
+    }
+
+    /// Orders two records from best to worst.
+    fn compare(&self, a: &Record, b: &Record) -> Ordering {
+        let primary = if self.program == ProgramId::VIQRC {
+            b.average().total_cmp(&a.average())
+        } else {
+            b.wp.cmp(&a.wp).then(b.ap.cmp(&a.ap)).then(b.sp.cmp(&a.sp))
+        };
+
+        // Scores are sorted from highest to lowest, so this compares the highest scores, then
+        // the second highest and so on.
+        primary.then_with(|| b.scores.cmp(&a.scores))
+    }
+}
+
+/// A team's results across the matches being ranked.
+#[derive(Debug)]
+struct Record {
+    team: IdInfo<TeamId>,
+    wins: i32,
+    losses: i32,
+    ties: i32,
+    wp: i32,
+    ap: i32,
+    sp: i32,
+    scores: Vec<i32>,
+}
+
+impl Record {
+    /// Updates the records of the teams playing (rather than sitting) on an alliance.
+    fn update_playing(
+        records: &mut BTreeMap<TeamId, Record>,
+        alliance: &Alliance,
+        mut update: impl FnMut(&mut Record),
+    ) {
+        for team in alliance.teams.iter().filter(|team| !team.sitting) {
+            let record = records.entry(team.team.id).or_insert_with(|| Record {
+                team: team.team.clone(),
+                wins: 0,
+                losses: 0,
+                ties: 0,
+                wp: 0,
+                ap: 0,
+                sp: 0,
+                scores: Vec::new(),
+            });
+            update(record);
+        }
+    }
+
+    fn average(&self) -> f64 {
+        if self.scores.is_empty() {
+            0.0
+        } else {
+            f64::from(self.scores.iter().sum::<i32>()) / self.scores.len() as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::matches::tests::MatchBuilder;
+
+    /// Every pair of teams 1-4 plays together once, plus a final that doesn't count.
+    fn matches() -> Vec<Match> {
+        vec![
+            MatchBuilder::qualification(1)
+                .red(&[1, 2], 30)
+                .blue(&[3, 4], 70)
+                .build(),
+            MatchBuilder::qualification(2)
+                .red(&[1, 3], 40)
+                .blue(&[2, 4], 60)
+                .build(),
+            MatchBuilder::qualification(3)
+                .red(&[1, 4], 50)
+                .blue(&[2, 3], 50)
+                .build(),
+            MatchBuilder::new(MatchRound::Finals, 1, 1)
+                .red(&[1, 2], 100)
+                .blue(&[3, 4], 0)
+                .build(),
+        ]
+    }
+
+    fn order(rankings: &[Ranking]) -> Vec<(i32, i32)> {
+        rankings
+            .iter()
+            .map(|ranking| (ranking.rank, ranking.team.id.0))
+            .collect()
+    }
+
+    #[test]
+    fn ranks_by_win_points() {
+        let rankings = RankingCalculator::new(ProgramId::V5RC).rank(&matches());
+
+        assert_eq!(order(&rankings), [(1, 4), (2, 3), (3, 2), (4, 1)]);
+
+        let team_4 = &rankings[0];
+        assert_eq!((team_4.wins, team_4.losses, team_4.ties), (2, 0, 1));
+        assert_eq!((team_4.wp, team_4.ap, team_4.sp), (5, 0, 120));
+        assert_eq!((team_4.high_score, team_4.total_points), (70, 180));
+        assert_eq!(team_4.average_points, 60.0);
+        assert_eq!(team_4.id, 0);
+        assert_eq!(
+            (team_4.event.id, team_4.division.id),
+            (EventId(1), DivisionId(1))
+        );
+    }
+
+    #[test]
+    fn autonomous_points_break_ties() {
+        // Teams 2 and 3 both have 3 WP and 120 SP. Team 3 has the higher match score, unless
+        // team 2 has more AP.
+        let rankings = RankingCalculator::new(ProgramId::V5RC)
+            .autonomous_points(TeamId(2), 8)
+            .rank(&matches());
+
+        assert_eq!(order(&rankings), [(1, 4), (2, 2), (3, 3), (4, 1)]);
+        assert_eq!(rankings[1].ap, 8);
+    }
+
+    #[test]
+    fn autonomous_win_points_add_to_win_points() {
+        // Team 1 lost twice and tied once, for 1 WP.
+        let rankings = RankingCalculator::new(ProgramId::V5RC)
+            .autonomous_win_points(TeamId(1), 5)
+            .rank(&matches());
+
+        assert_eq!(order(&rankings), [(1, 1), (2, 4), (3, 3), (4, 2)]);
+        assert_eq!(
+            (rankings[0].wins, rankings[0].ties, rankings[0].wp),
+            (0, 1, 6)
+        );
+    }
+
+    #[test]
+    fn autonomous_win_points_are_ignored_in_viqrc() {
+        let matches = [MatchBuilder::qualification(1).red(&[1, 2], 30).build()];
+
+        let rankings = RankingCalculator::new(ProgramId::VIQRC)
+            .autonomous_win_points(TeamId(1), 5)
+            .rank(&matches);
+
+        assert!(rankings.iter().all(|ranking| ranking.wp == 0));
+    }
+
+    #[test]
+    fn autonomous_points_from_rankings() {
+        let existing = RankingCalculator::new(ProgramId::V5RC)
+            .autonomous_points(TeamId(2), 8)
+            .autonomous_points(TeamId(4), 2)
+            .autonomous_win_points(TeamId(3), 1)
+            .rank(&matches());
+
+        let rankings = RankingCalculator::new(ProgramId::V5RC)
+            .autonomous_points_from(&existing)
+            .rank(&matches());
+
+        assert_eq!(rankings, existing);
+        let points: Vec<_> = rankings.iter().map(|r| (r.team.id.0, r.wp, r.ap)).collect();
+        assert_eq!(points, [(4, 5, 2), (3, 4, 0), (2, 3, 8), (1, 1, 0)]);
+    }
+
+    #[test]
+    fn autonomous_win_points_from_api_rankings() {
+        // RobotEvents includes AWPs in the WP it reports, without listing them separately.
+        let mut existing = RankingCalculator::new(ProgramId::V5RC).rank(&matches());
+        for ranking in &mut existing {
+            if ranking.team.id == TeamId(1) {
+                ranking.wp += 5;
+            }
+        }
+
+        let rankings = RankingCalculator::new(ProgramId::V5RC)
+            .autonomous_points_from(&existing)
+            .rank(&matches());
+
+        assert_eq!(order(&rankings), [(1, 1), (2, 4), (3, 3), (4, 2)]);
+        assert_eq!(rankings[0].wp, 6);
+    }
+
+    #[test]
+    fn viqrc_ranks_by_average_score() {
+        // VIQRC alliances are two teams working together, with no opponent.
+        let matches = [
+            MatchBuilder::qualification(1).red(&[1, 2], 30).build(),
+            MatchBuilder::qualification(2).red(&[1, 3], 50).build(),
+            MatchBuilder::qualification(3).red(&[2, 3], 40).build(),
+        ];
+
+        let rankings = RankingCalculator::new(ProgramId::VIQRC).rank(&matches);
+
+        assert_eq!(order(&rankings), [(1, 3), (2, 1), (3, 2)]);
+        let averages: Vec<_> = rankings.iter().map(|r| r.average_points).collect();
+        assert_eq!(averages, [45.0, 40.0, 35.0]);
+        assert!(rankings
+            .iter()
+            .all(|r| (r.wins, r.losses, r.ties, r.wp, r.sp) == (0, 0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn viqrc_ties_go_to_the_highest_score() {
+        let matches = [
+            MatchBuilder::qualification(1).red(&[1, 2], 40).build(),
+            MatchBuilder::qualification(2).red(&[1, 3], 40).build(),
+            MatchBuilder::qualification(3).red(&[2, 4], 30).build(),
+            MatchBuilder::qualification(4).red(&[3, 4], 50).build(),
+        ];
+
+        let rankings = RankingCalculator::new(ProgramId::VIQRC).rank(&matches);
+
+        // Teams 1 and 4 both average 40, but team 4's best match was better.
+        assert_eq!(order(&rankings), [(1, 3), (2, 4), (3, 1), (4, 2)]);
+    }
+
+    #[test]
+    fn ignores_other_divisions() {
+        let mut matches = matches();
+        matches.push(
+            MatchBuilder::qualification(1)
+                .event(1, 2)
+                .red(&[1, 5], 100)
+                .blue(&[2, 6], 0)
+                .build(),
+        );
+        matches.push(
+            MatchBuilder::qualification(1)
+                .event(2, 1)
+                .red(&[1, 7], 100)
+                .blue(&[2, 8], 0)
+                .build(),
+        );
+
+        let rankings = RankingCalculator::new(ProgramId::V5RC).rank(&matches);
+
+        assert_eq!(order(&rankings), [(1, 4), (2, 3), (3, 2), (4, 1)]);
+    }
+
+    #[test]
+    fn excludes_sitting_teams() {
+        let mut matches = matches();
+        matches[0] = MatchBuilder::qualification(1)
+            .red(&[1, 2, 5], 30)
+            .blue(&[3, 4], 70)
+            .sitting(5)
+            .build();
+
+        let rankings = RankingCalculator::new(ProgramId::V5RC).rank(&matches);
+
+        assert_eq!(order(&rankings), [(1, 4), (2, 3), (3, 2), (4, 1)]);
+    }
+
+    #[test]
+    fn nothing_to_rank() {
+        let calculator = RankingCalculator::new(ProgramId::V5RC);
+        assert!(calculator.rank(&[]).is_empty());
+
+        let unscored = MatchBuilder::qualification(1)
+            .red(&[1, 2], 0)
+            .blue(&[3, 4], 0)
+            .unscored()
+            .build();
+        assert!(calculator.rank(&[unscored]).is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+//! A blocking RobotEvents API client.
+//!
+//! [`RobotEvents`] wraps the asynchronous [`crate::RobotEvents`] client, running each request
+//! to completion on its own single-threaded Tokio runtime. It has the same endpoint methods and
+//! takes the same query builders, returning the same [`schema`](crate::schema) types.
+//!
+//! Lazy `*_stream` methods aren't available; use the paginated methods or the `all_*`
+//! collectors instead.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use robotevents::{blocking::RobotEvents, query::TeamsQuery};
+//!
+//! let client = RobotEvents::new(std::env::var("ROBOTEVENTS_TOKEN")?)?;
+//! let teams = client.teams(TeamsQuery::new().number("1082R".parse()?))?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Panics
+//!
+//! Calling any method from within an asynchronous runtime panics, since it would block one of
+//! the runtime's threads. Async code should use [`crate::RobotEvents`] directly.
+
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use tokio::runtime::Runtime;
+
+use crate::{
+    client::{error, rate_limit::RateLimiterStats},
+    query::{
+        DivisionMatchesQuery, DivisionRankingsQuery, EventAwardsQuery, EventSkillsQuery,
+        EventTeamsQuery, EventsQuery, SeasonEventsQuery, SeasonsQuery, TeamAwardsQuery,
+        TeamEventsQuery, TeamMatchesQuery, TeamRankingsQuery, TeamSkillsQuery, TeamsQuery,
+    },
+    schema::*,
+};
+
+/// A blocking RobotEvents API client.
+///
+/// Clones share the same runtime and the underlying client's cache and rate limiter.
+#[derive(Debug, Clone)]
+pub struct RobotEvents {
+    inner: crate::RobotEvents,
+    runtime: Arc<Runtime>,
+}
+
+/// Defines blocking versions of [`crate::RobotEvents`] methods with the same signatures.
+macro_rules! blocking_methods {
+    ($(fn $name:ident(&self $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty;)*) => {
+        $(
+            #[doc = concat!("Blocking version of [`crate::RobotEvents::", stringify!($name), "`].")]
+            pub fn $name(&self $(, $arg: $ty)*) -> Result<$ret, error::Error> {
+                self.runtime.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl RobotEvents {
+    /// Creates a new blocking RobotEvents API client.
+    ///
+    /// See [`crate::RobotEvents::new`].
+    pub fn new(bearer_token: impl AsRef<str>) -> Result<Self, error::Error> {
+        Self::from_async(crate::RobotEvents::new(bearer_token))
+    }
+
+    /// Creates a blocking client from a configured asynchronous client, such as one created
+    /// with [`crate::RobotEvents::builder`].
+    pub fn from_async(client: crate::RobotEvents) -> Result<Self, error::Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(error::Error::RuntimeError)?;
+
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The asynchronous client used to make requests.
+    pub fn as_async(&self) -> &crate::RobotEvents {
+        &self.inner
+    }
+
+    /// Returns a copy of the client that doesn't serve requests from its cache.
+    ///
+    /// See [`crate::RobotEvents::no_cache`].
+    pub fn no_cache(&self) -> Self {
+        Self {
+            inner: self.inner.no_cache(),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    /// See [`crate::RobotEvents::save_cassette`].
+    pub fn save_cassette(&self) -> Result<(), error::Error> {
+        self.inner.save_cassette()
+    }
+
+    /// See [`crate::RobotEvents::clear_cache`].
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache()
+    }
+
+    /// See [`crate::RobotEvents::rate_limiter_stats`].
+    pub fn rate_limiter_stats(&self) -> Option<RateLimiterStats> {
+        self.inner.rate_limiter_stats()
+    }
+
+    /// Blocking version of [`crate::RobotEvents::request_json`].
+    pub fn request_json<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<T, error::Error> {
+        self.runtime.block_on(self.inner.request_json(endpoint))
+    }
+
+    /// Blocking version of [`crate::RobotEvents::request_page`].
+    pub fn request_page<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<PaginatedResponse<T>, error::Error> {
+        self.runtime.block_on(self.inner.request_page(endpoint))
+    }
+
+    /// Blocking version of [`crate::RobotEvents::request_api_v1_json`].
+    pub fn request_api_v1_json<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<T, error::Error> {
+        self.runtime
+            .block_on(self.inner.request_api_v1_json(endpoint))
+    }
+
+    blocking_methods! {
+        fn teams(&self, query: TeamsQuery) -> PaginatedResponse<Team>;
+        fn all_teams(&self, query: TeamsQuery) -> Vec<Team>;
+        fn team(&self, team_id: TeamId) -> Team;
+        fn team_by_number(&self, number: impl AsRef<str>, program: ProgramId) -> Option<Team>;
+        fn team_events(&self, team_id: TeamId, query: TeamEventsQuery) -> PaginatedResponse<Event>;
+        fn all_team_events(&self, team_id: TeamId, query: TeamEventsQuery) -> Vec<Event>;
+        fn team_matches(
+            &self,
+            team_id: TeamId,
+            query: TeamMatchesQuery,
+        ) -> PaginatedResponse<Match>;
+        fn all_team_matches(&self, team_id: TeamId, query: TeamMatchesQuery) -> Vec<Match>;
+        fn team_rankings(
+            &self,
+            team_id: TeamId,
+            query: TeamRankingsQuery,
+        ) -> PaginatedResponse<Ranking>;
+        fn all_team_rankings(&self, team_id: TeamId, query: TeamRankingsQuery) -> Vec<Ranking>;
+        fn team_skills(&self, team_id: TeamId, query: TeamSkillsQuery) -> PaginatedResponse<Skill>;
+        fn all_team_skills(&self, team_id: TeamId, query: TeamSkillsQuery) -> Vec<Skill>;
+        fn team_awards(&self, team_id: TeamId, query: TeamAwardsQuery) -> PaginatedResponse<Award>;
+        fn all_team_awards(&self, team_id: TeamId, query: TeamAwardsQuery) -> Vec<Award>;
+
+        fn seasons(&self, query: SeasonsQuery) -> PaginatedResponse<Season>;
+        fn all_seasons(&self, query: SeasonsQuery) -> Vec<Season>;
+        fn season(&self, season_id: SeasonId) -> Season;
+        fn season_events(
+            &self,
+            season_id: SeasonId,
+            query: SeasonEventsQuery,
+        ) -> PaginatedResponse<Event>;
+        fn all_season_events(&self, season_id: SeasonId, query: SeasonEventsQuery) -> Vec<Event>;
+        fn world_skills(
+            &self,
+            season_id: SeasonId,
+            grade: Grade,
+            post_season: bool,
+        ) -> Vec<v1::SkillsStanding>;
+
+        fn programs(&self) -> PaginatedResponse<IdInfo<ProgramId>>;
+        fn all_programs(&self) -> Vec<IdInfo<ProgramId>>;
+        fn program(&self, program_id: ProgramId) -> IdInfo<ProgramId>;
+
+        fn events(&self, query: EventsQuery) -> PaginatedResponse<Event>;
+        fn all_events(&self, query: EventsQuery) -> Vec<Event>;
+        fn event(&self, event_id: EventId) -> Event;
+        fn event_by_sku(&self, sku: impl AsRef<str>) -> Option<Event>;
+        fn event_teams(&self, event_id: EventId, query: EventTeamsQuery) -> PaginatedResponse<Team>;
+        fn all_event_teams(&self, event_id: EventId, query: EventTeamsQuery) -> Vec<Team>;
+        fn event_skills(
+            &self,
+            event_id: EventId,
+            query: EventSkillsQuery,
+        ) -> PaginatedResponse<Skill>;
+        fn all_event_skills(&self, event_id: EventId, query: EventSkillsQuery) -> Vec<Skill>;
+        fn event_awards(
+            &self,
+            event_id: EventId,
+            query: EventAwardsQuery,
+        ) -> PaginatedResponse<Award>;
+        fn all_event_awards(&self, event_id: EventId, query: EventAwardsQuery) -> Vec<Award>;
+        fn event_division_matches(
+            &self,
+            event_id: EventId,
+            division_id: DivisionId,
+            query: DivisionMatchesQuery,
+        ) -> PaginatedResponse<Match>;
+        fn all_event_division_matches(
+            &self,
+            event_id: EventId,
+            division_id: DivisionId,
+            query: DivisionMatchesQuery,
+        ) -> Vec<Match>;
+        fn event_division_finalist_rankings(
+            &self,
+            event_id: EventId,
+            division_id: DivisionId,
+            query: DivisionRankingsQuery,
+        ) -> PaginatedResponse<Ranking>;
+        fn all_event_division_finalist_rankings(
+            &self,
+            event_id: EventId,
+            division_id: DivisionId,
+            query: DivisionRankingsQuery,
+        ) -> Vec<Ranking>;
+        fn event_division_rankings(
+            &self,
+            event_id: EventId,
+            division_id: DivisionId,
+            query: DivisionRankingsQuery,
+        ) -> PaginatedResponse<Ranking>;
+        fn all_event_division_rankings(
+            &self,
+            event_id: EventId,
+            division_id: DivisionId,
+            query: DivisionRankingsQuery,
+        ) -> Vec<Ranking>;
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::{tests::team, MockData, MockServer};
+
+    fn server(teams: i32) -> MockServer {
+        MockServer::start(MockData {
+            teams: (1..=teams).map(team).collect(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn client(server: &MockServer) -> RobotEvents {
+        RobotEvents::from_async(server.client_builder("token").build().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn single_records() {
+        let server = server(1);
+
+        assert_eq!(client(&server).team(TeamId(1)).unwrap(), team(1));
+        assert_eq!(server.requests(), ["/api/v2/teams/1"]);
+    }
+
+    #[test]
+    fn paginated_records() {
+        let server = server(30);
+        let client = client(&server);
+
+        let page = client.teams(TeamsQuery::new()).unwrap();
+        assert_eq!(page.data.len(), 25);
+        assert_eq!(page.meta.last_page, 2);
+
+        let teams = client.all_teams(TeamsQuery::new()).unwrap();
+        let ids: Vec<_> = teams.iter().map(|team| team.id.0).collect();
+        assert_eq!(ids, (1..=30).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn errors_are_returned() {
+        let server = server(1);
+
+        let error = client(&server).team(TeamId(2)).unwrap_err();
+
+        assert!(matches!(error, error::Error::NotFound { .. }));
+    }
+
+    #[test]
+    fn clones_share_the_runtime() {
+        let server = server(1);
+        let client = client(&server);
+        let uncached = client.no_cache();
+
+        assert!(Arc::ptr_eq(&client.runtime, &uncached.runtime));
+        assert_eq!(uncached.team(TeamId(1)).unwrap(), team(1));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Cannot start a runtime from within a runtime")]
+    async fn calls_from_an_async_runtime_panic() {
+        let server = server(1);
+        let _ = client(&server).team(TeamId(1));
+    }
+}
@@ -0,0 +1,616 @@
+//! Caching API responses.
+//!
+//! A client built with [`RobotEventsBuilder::cache`](super::RobotEventsBuilder::cache) keeps
+//! successful responses in a [`CacheBackend`] and serves repeated requests from it until they
+//! expire. How long a response stays fresh depends on the [`ResourceKind`] it was requested
+//! for, since some resources (such as seasons) almost never change while others (such as live
+//! rankings) change every few minutes. See [`CachePolicy`] for the defaults.
+//!
+//! Responses are stored under their endpoint path along with a hash of the bearer token they
+//! were requested with, so clients with different tokens can share a backend without being
+//! served each other's responses.
+//!
+//! Two backends are provided: [`MemoryCache`], an in-memory LRU cache, and [`DiskCache`], which
+//! stores each response as a file in a directory and survives restarts.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::cassette::RecordedResponse;
+
+/// A response stored in a cache, along with when it was stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub response: RecordedResponse,
+    pub stored_at: SystemTime,
+}
+
+impl CachedResponse {
+    /// Whether the response is still fresh for a time-to-live of `ttl`.
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        self.stored_at
+            .elapsed()
+            .map(|age| age < ttl)
+            // The clock went backwards since the response was stored.
+            .unwrap_or(true)
+    }
+}
+
+/// Storage for cached responses, keyed by the requested endpoint path and bearer token.
+///
+/// Backends don't need to track expiry themselves; the client checks each entry against the
+/// [`CachePolicy`] when it is read.
+pub trait CacheBackend: Debug + Send + Sync {
+    /// Gets the response stored for `key`, if there is one.
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// Stores a response for `key`, replacing any existing entry.
+    fn insert(&self, key: &str, response: CachedResponse);
+
+    /// Removes the response stored for `key`.
+    fn remove(&self, key: &str);
+
+    /// Removes every stored response.
+    fn clear(&self);
+}
+
+impl<T: CacheBackend + ?Sized> CacheBackend for Arc<T> {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        (**self).get(key)
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        (**self).insert(key, response)
+    }
+
+    fn remove(&self, key: &str) {
+        (**self).remove(key)
+    }
+
+    fn clear(&self) {
+        (**self).clear()
+    }
+}
+
+/// The kind of resource an endpoint returns, used to pick how long its responses are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Programs,
+    Seasons,
+    Events,
+    Teams,
+    Matches,
+    Rankings,
+    Skills,
+    Awards,
+    /// An endpoint this version of the crate doesn't know about.
+    Other,
+}
+
+impl ResourceKind {
+    /// Works out which kind of resource an endpoint path such as `/events/1/divisions/2/rankings`
+    /// returns, from the last segment of the path that names a kind of resource.
+    pub fn of_endpoint(endpoint: &str) -> Self {
+        let path = endpoint.split('?').next().unwrap_or_default();
+
+        path.split('/')
+            .rev()
+            .map(Self::of_segment)
+            .find(|kind| *kind != Self::Other)
+            .unwrap_or(Self::Other)
+    }
+
+    fn of_segment(segment: &str) -> Self {
+        match segment {
+            "programs" => Self::Programs,
+            "seasons" => Self::Seasons,
+            "events" | "divisions" => Self::Events,
+            "teams" => Self::Teams,
+            "matches" => Self::Matches,
+            "rankings" | "finalistRankings" => Self::Rankings,
+            "skills" => Self::Skills,
+            "awards" => Self::Awards,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// How long responses for each [`ResourceKind`] stay fresh.
+///
+/// By default, programs and seasons are cached for a day, teams for an hour, events and awards
+/// for ten minutes, and matches, rankings and skills for one minute. A TTL of
+/// [`Duration::ZERO`] disables caching for that kind of resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub ttls: HashMap<ResourceKind, Duration>,
+    /// The TTL for resource kinds that aren't in `ttls`.
+    pub default_ttl: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        const MINUTE: Duration = Duration::from_secs(60);
+
+        Self {
+            ttls: HashMap::from([
+                (ResourceKind::Programs, 24 * 60 * MINUTE),
+                (ResourceKind::Seasons, 24 * 60 * MINUTE),
+                (ResourceKind::Teams, 60 * MINUTE),
+                (ResourceKind::Events, 10 * MINUTE),
+                (ResourceKind::Awards, 10 * MINUTE),
+                (ResourceKind::Matches, MINUTE),
+                (ResourceKind::Rankings, MINUTE),
+                (ResourceKind::Skills, MINUTE),
+            ]),
+            default_ttl: MINUTE,
+        }
+    }
+}
+
+impl CachePolicy {
+    /// Sets the TTL for a kind of resource.
+    pub fn ttl(mut self, kind: ResourceKind, ttl: Duration) -> Self {
+        self.ttls.insert(kind, ttl);
+        self
+    }
+
+    /// Gets the TTL for a kind of resource.
+    pub fn ttl_for(&self, kind: ResourceKind) -> Duration {
+        self.ttls.get(&kind).copied().unwrap_or(self.default_ttl)
+    }
+}
+
+/// A [`CacheBackend`] and the [`CachePolicy`] used with it.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    pub(crate) backend: Arc<dyn CacheBackend>,
+    pub(crate) policy: CachePolicy,
+}
+
+impl ResponseCache {
+    /// The key a response is stored under in the backend: the hash of the bearer token, then
+    /// the endpoint path.
+    fn key(token: &str, endpoint: &str) -> String {
+        format!("{:016x}:{endpoint}", fnv1a(token))
+    }
+
+    /// Gets a fresh response for an endpoint requested with `token` from the cache.
+    pub(crate) fn get(&self, token: &str, endpoint: &str) -> Option<RecordedResponse> {
+        let ttl = self.policy.ttl_for(ResourceKind::of_endpoint(endpoint));
+        if ttl.is_zero() {
+            return None;
+        }
+
+        let key = Self::key(token, endpoint);
+        match self.backend.get(&key) {
+            Some(cached) if cached.is_fresh(ttl) => Some(cached.response),
+            Some(_) => {
+                self.backend.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Stores a response for an endpoint requested with `token`, unless its kind of resource
+    /// isn't cached.
+    pub(crate) fn insert(&self, token: &str, endpoint: &str, response: &RecordedResponse) {
+        if self
+            .policy
+            .ttl_for(ResourceKind::of_endpoint(endpoint))
+            .is_zero()
+        {
+            return;
+        }
+
+        self.backend.insert(
+            &Self::key(token, endpoint),
+            CachedResponse {
+                response: response.clone(),
+                stored_at: SystemTime::now(),
+            },
+        );
+    }
+}
+
+/// An in-memory cache that evicts the least recently used response once it is full.
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    entries: Mutex<MemoryEntries>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryEntries {
+    /// Each response along with the tick it was last used at.
+    responses: HashMap<String, (CachedResponse, u64)>,
+    tick: u64,
+}
+
+impl MemoryCache {
+    /// Creates a cache that holds up to `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(MemoryEntries::default()),
+        }
+    }
+
+    /// The number of responses in the cache.
+    pub fn len(&self) -> usize {
+        self.entries().responses.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, MemoryEntries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CacheBackend for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries();
+        entries.tick += 1;
+        let tick = entries.tick;
+
+        let (response, last_used) = entries.responses.get_mut(key)?;
+        *last_used = tick;
+        Some(response.clone())
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries();
+        entries.tick += 1;
+        let tick = entries.tick;
+
+        if !entries.responses.contains_key(key) && entries.responses.len() >= self.capacity {
+            let least_recent = entries
+                .responses
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recent) = least_recent {
+                entries.responses.remove(&least_recent);
+            }
+        }
+        entries.responses.insert(key.to_owned(), (response, tick));
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries().responses.remove(key);
+    }
+
+    fn clear(&self) {
+        self.entries().responses.clear();
+    }
+}
+
+/// A cache that stores each response as a JSON file in a directory.
+///
+/// Entries persist across runs of the program. Errors reading or writing the cache directory
+/// are treated as cache misses.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    #[serde(flatten)]
+    cached: CachedResponse,
+}
+
+impl DiskCache {
+    /// Creates a cache in `dir`, creating the directory if it doesn't exist.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(key)))
+    }
+}
+
+impl CacheBackend for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let entry: DiskEntry =
+            serde_json::from_str(&fs::read_to_string(self.path(key)).ok()?).ok()?;
+        // Another key with the same hash may have been stored in this file.
+        (entry.key == key).then_some(entry.cached)
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        let entry = DiskEntry {
+            key: key.to_owned(),
+            cached: response,
+        };
+        if let Ok(json) = serde_json::to_string(&entry) {
+            let _ = fs::write(self.path(key), json);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+
+    fn clear(&self) {
+        let Ok(files) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// The 64-bit FNV-1a hash of a string, used to name cache files and scope keys to a bearer
+/// token. Unlike
+/// [`std::hash::DefaultHasher`], it is stable across Rust versions.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(body: &str, age: Duration) -> CachedResponse {
+        CachedResponse {
+            response: RecordedResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: body.to_owned(),
+            },
+            stored_at: SystemTime::now() - age,
+        }
+    }
+
+    fn body(backend: &impl CacheBackend, key: &str) -> Option<String> {
+        backend.get(key).map(|cached| cached.response.body)
+    }
+
+    #[test]
+    fn resource_kinds_of_endpoints() {
+        let kinds = [
+            ("/programs", ResourceKind::Programs),
+            ("/seasons/190", ResourceKind::Seasons),
+            (
+                "/seasons/190/events?level%5B%5D=World",
+                ResourceKind::Events,
+            ),
+            ("/events/1/divisions/2", ResourceKind::Events),
+            ("/events/1/divisions/2/matches", ResourceKind::Matches),
+            (
+                "/events/1/divisions/2/rankings?rank%5B%5D=1",
+                ResourceKind::Rankings,
+            ),
+            (
+                "/events/1/divisions/2/finalistRankings",
+                ResourceKind::Rankings,
+            ),
+            ("/teams?number%5B%5D=1082R", ResourceKind::Teams),
+            ("/teams/1/skills", ResourceKind::Skills),
+            ("/teams/1/awards/", ResourceKind::Awards),
+            (
+                "v1:/seasons/190/skills?grade_level=High%20School",
+                ResourceKind::Skills,
+            ),
+            ("v1:/teams/1082R", ResourceKind::Teams),
+            ("/unknown/1", ResourceKind::Other),
+            ("", ResourceKind::Other),
+        ];
+
+        for (endpoint, kind) in kinds {
+            assert_eq!(ResourceKind::of_endpoint(endpoint), kind, "{endpoint}");
+        }
+    }
+
+    #[test]
+    fn memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        cache.insert("a", cached("a", Duration::ZERO));
+        cache.insert("b", cached("b", Duration::ZERO));
+
+        // Reading "a" makes "b" the least recently used.
+        assert_eq!(body(&cache, "a").as_deref(), Some("a"));
+        cache.insert("c", cached("c", Duration::ZERO));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(body(&cache, "b"), None);
+        assert_eq!(body(&cache, "a").as_deref(), Some("a"));
+        assert_eq!(body(&cache, "c").as_deref(), Some("c"));
+
+        // Replacing an entry doesn't evict anything.
+        cache.insert("c", cached("c2", Duration::ZERO));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(body(&cache, "c").as_deref(), Some("c2"));
+    }
+
+    #[test]
+    fn memory_cache_with_no_capacity_stores_nothing() {
+        let cache = MemoryCache::new(0);
+        cache.insert("a", cached("a", Duration::ZERO));
+
+        assert!(cache.is_empty());
+        assert_eq!(body(&cache, "a"), None);
+    }
+
+    #[test]
+    fn expired_responses_are_removed() {
+        let backend = Arc::new(MemoryCache::new(10));
+        let cache = ResponseCache {
+            backend: backend.clone(),
+            policy: CachePolicy::default(),
+        };
+        let rankings = "/events/1/divisions/1/rankings";
+        backend.insert(
+            &ResponseCache::key("token", "/teams/1"),
+            cached("fresh", Duration::from_secs(30)),
+        );
+        backend.insert(
+            &ResponseCache::key("token", rankings),
+            cached("stale", Duration::from_secs(90)),
+        );
+
+        assert_eq!(
+            cache.get("token", "/teams/1").map(|r| r.body).as_deref(),
+            Some("fresh")
+        );
+        assert_eq!(cache.get("token", rankings), None);
+        assert_eq!(body(&backend, &ResponseCache::key("token", rankings)), None);
+        assert_eq!(backend.len(), 1);
+    }
+
+    #[test]
+    fn zero_ttl_bypasses_the_cache() {
+        let backend = Arc::new(MemoryCache::new(10));
+        let cache = ResponseCache {
+            backend: backend.clone(),
+            policy: CachePolicy::default().ttl(ResourceKind::Teams, Duration::ZERO),
+        };
+
+        cache.insert("token", "/teams/1", &cached("new", Duration::ZERO).response);
+        assert!(backend.is_empty());
+
+        // Entries stored before the TTL was set to zero are ignored but left alone.
+        backend.insert(
+            &ResponseCache::key("token", "/teams/1"),
+            cached("old", Duration::ZERO),
+        );
+        assert_eq!(cache.get("token", "/teams/1"), None);
+        assert_eq!(backend.len(), 1);
+    }
+
+    #[test]
+    fn responses_are_scoped_to_the_token() {
+        let cache = ResponseCache {
+            backend: Arc::new(MemoryCache::new(10)),
+            policy: CachePolicy::default(),
+        };
+        cache.insert("token-a", "/teams/1", &cached("a", Duration::ZERO).response);
+
+        assert_eq!(
+            cache.get("token-a", "/teams/1").map(|r| r.body).as_deref(),
+            Some("a")
+        );
+        assert_eq!(cache.get("token-b", "/teams/1"), None);
+        assert_ne!(
+            ResponseCache::key("token-a", "/teams/1"),
+            ResponseCache::key("token-b", "/teams/1")
+        );
+    }
+
+    #[test]
+    fn disk_cache_checks_the_stored_key() {
+        let dir = std::env::temp_dir().join(format!("robotevents-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&dir).unwrap();
+
+        cache.insert("/teams/1", cached("team", Duration::ZERO));
+        assert_eq!(body(&cache, "/teams/1").as_deref(), Some("team"));
+
+        // Simulate "/teams/2" hashing to the same file as "/teams/1".
+        fs::copy(cache.path("/teams/1"), cache.path("/teams/2")).unwrap();
+        assert_eq!(body(&cache, "/teams/2"), None);
+
+        // Entries survive being reopened, until they're cleared.
+        let reopened = DiskCache::new(&dir).unwrap();
+        assert_eq!(body(&reopened, "/teams/1").as_deref(), Some("team"));
+        reopened.clear();
+        assert_eq!(body(&cache, "/teams/1"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn no_cache_refreshes_the_cached_response() {
+        use crate::{
+            mock::{tests::team, MockData, MockServer},
+            schema::TeamId,
+        };
+
+        let mut data = MockData {
+            teams: vec![team(1)],
+            ..Default::default()
+        };
+        let server = MockServer::start(data.clone()).unwrap();
+        let client = server
+            .client_builder("token")
+            .cache(MemoryCache::new(10))
+            .build()
+            .unwrap();
+
+        assert_eq!(client.team(TeamId(1)).await.unwrap().team_name, "Team 1");
+
+        data.teams[0].team_name = "Renamed".to_owned();
+        server.set_data(data);
+        assert_eq!(client.team(TeamId(1)).await.unwrap().team_name, "Team 1");
+        assert_eq!(server.requests().len(), 1);
+
+        let refreshed = client.no_cache().team(TeamId(1)).await.unwrap();
+        assert_eq!(refreshed.team_name, "Renamed");
+        assert_eq!(client.team(TeamId(1)).await.unwrap().team_name, "Renamed");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn shared_caches_keep_tokens_apart() {
+        use crate::{
+            mock::{tests::team, MockData, MockServer},
+            schema::TeamId,
+        };
+
+        let server = MockServer::start(MockData {
+            teams: vec![team(1)],
+            ..Default::default()
+        })
+        .unwrap();
+        let backend = Arc::new(MemoryCache::new(10));
+        let client = |token| {
+            server
+                .client_builder(token)
+                .cache(backend.clone())
+                .build()
+                .unwrap()
+        };
+        let (a, b) = (client("token-a"), client("token-b"));
+
+        a.team(TeamId(1)).await.unwrap();
+        a.team(TeamId(1)).await.unwrap();
+        assert_eq!(server.requests().len(), 1);
+
+        // The second token isn't served the first token's response.
+        b.team(TeamId(1)).await.unwrap();
+        b.team(TeamId(1)).await.unwrap();
+        assert_eq!(server.requests().len(), 2);
+        assert_eq!(backend.len(), 2);
+    }
+}
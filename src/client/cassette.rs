@@ -0,0 +1,370 @@
+//! Recording and replaying API responses.
+//!
+//! A [`Cassette`] is a list of requests made by a [`RobotEvents`](super::RobotEvents) client
+//! along with the responses the API gave. A client built with
+//! [`RobotEventsBuilder::record_to`](super::RobotEventsBuilder::record_to) records every
+//! response it receives and saves them to a cassette file, and one built with
+//! [`RobotEventsBuilder::replay_from`](super::RobotEventsBuilder::replay_from) serves requests
+//! from that file without touching the network.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::error;
+
+/// A response as returned by the API, stored so that it can be served again later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedResponse {
+    /// Reads a [`reqwest::Response`] into memory.
+    pub(crate) async fn from_response(response: reqwest::Response) -> Result<Self, reqwest::Error> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let body = response.text().await?;
+
+        Ok(Self {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Rebuilds a [`reqwest::Response`] for the given URL from the recording.
+    pub(crate) fn to_response(&self, url: &str) -> reqwest::Response {
+        use reqwest::ResponseBuilderExt;
+
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Ok(url) = reqwest::Url::parse(url) {
+            builder = builder.url(url);
+        }
+
+        match builder.body(self.body.clone()) {
+            Ok(response) => response.into(),
+            // Only reachable with a hand-edited recording containing an invalid status or
+            // header, in which case the body alone is the most useful thing to return.
+            Err(_) => http::Response::new(self.body.clone()).into(),
+        }
+    }
+}
+
+/// A single request made by the client and the response it received.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The requested endpoint path, including its query string.
+    pub path: String,
+    pub response: RecordedResponse,
+}
+
+/// A list of recorded [`Interaction`]s, stored as JSON.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Loads a cassette from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Saves the cassette to a JSON file, replacing it if it already exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(path, json)
+    }
+
+    /// Finds the recorded path that is most similar to `path`.
+    pub fn closest_path(&self, path: &str) -> Option<&str> {
+        self.interactions
+            .iter()
+            .map(|interaction| interaction.path.as_str())
+            .min_by_key(|recorded| edit_distance(recorded, path))
+    }
+}
+
+/// Whether a client is recording to or replaying from a cassette.
+#[derive(Debug)]
+pub(crate) enum CassetteMode {
+    Record {
+        path: PathBuf,
+        cassette: Mutex<Cassette>,
+    },
+    Replay {
+        cassette: Cassette,
+        /// How many times each interaction has been served.
+        served: Mutex<Vec<usize>>,
+    },
+}
+
+impl CassetteMode {
+    pub(crate) fn record(path: PathBuf) -> Self {
+        Self::Record {
+            path,
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    pub(crate) fn replay(path: &Path) -> io::Result<Self> {
+        let cassette = Cassette::load(path)?;
+        let served = Mutex::new(vec![0; cassette.interactions.len()]);
+
+        Ok(Self::Replay { cassette, served })
+    }
+
+    /// Adds an interaction to the cassette being recorded.
+    ///
+    /// The cassette is only kept in memory until [`Self::save`] is called.
+    pub(crate) fn record_interaction(&self, path: &str, response: &RecordedResponse) {
+        if let Self::Record { cassette, .. } = self {
+            cassette
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .interactions
+                .push(Interaction {
+                    path: path.to_owned(),
+                    response: response.clone(),
+                });
+        }
+    }
+
+    /// Writes the cassette being recorded to its file.
+    pub(crate) fn save(&self) -> io::Result<()> {
+        match self {
+            Self::Record { path, cassette } => cassette
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .save(path),
+            Self::Replay { .. } => Ok(()),
+        }
+    }
+
+    /// Finds the recorded response for a request when replaying.
+    ///
+    /// Interactions recorded for the same path are served in the order they were
+    /// recorded. Once they have all been served, the last one is served again.
+    pub(crate) fn replay_interaction(
+        &self,
+        path: &str,
+    ) -> Option<Result<RecordedResponse, error::Error>> {
+        let Self::Replay { cassette, served } = self else {
+            return None;
+        };
+        let mut served = served.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let matching: Vec<usize> = cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.path == path)
+            .map(|(i, _)| i)
+            .collect();
+        let Some(&last) = matching.last() else {
+            return Some(Err(error::Error::CassetteMiss {
+                endpoint: path.to_owned(),
+                closest: cassette.closest_path(path).map(str::to_owned),
+            }));
+        };
+        let i = matching
+            .into_iter()
+            .find(|&i| served[i] == 0)
+            .unwrap_or(last);
+        served[i] += 1;
+
+        Some(Ok(cassette.interactions[i].response.clone()))
+    }
+}
+
+impl Drop for CassetteMode {
+    fn drop(&mut self) {
+        // Errors can only be reported by saving explicitly with `RobotEvents::save_cassette`.
+        let _ = self.save();
+    }
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction(path: &str, body: &str) -> Interaction {
+        Interaction {
+            path: path.to_owned(),
+            response: RecordedResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: body.to_owned(),
+            },
+        }
+    }
+
+    fn replay(interactions: Vec<Interaction>) -> CassetteMode {
+        let served = Mutex::new(vec![0; interactions.len()]);
+        CassetteMode::Replay {
+            cassette: Cassette { interactions },
+            served,
+        }
+    }
+
+    fn body(mode: &CassetteMode, path: &str) -> String {
+        mode.replay_interaction(path).unwrap().unwrap().body
+    }
+
+    #[test]
+    fn repeated_paths_are_served_in_order() {
+        let mode = replay(vec![
+            interaction("/teams/1", "first"),
+            interaction("/teams/2", "other"),
+            interaction("/teams/1", "second"),
+        ]);
+
+        assert_eq!(body(&mode, "/teams/1"), "first");
+        assert_eq!(body(&mode, "/teams/1"), "second");
+        // Once every recording has been served, the last one is repeated.
+        assert_eq!(body(&mode, "/teams/1"), "second");
+        assert_eq!(body(&mode, "/teams/2"), "other");
+    }
+
+    #[test]
+    fn misses_report_the_closest_path() {
+        let mode = replay(vec![
+            interaction("/teams/1234", ""),
+            interaction("/events/1234", ""),
+        ]);
+
+        let Some(Err(error::Error::CassetteMiss { endpoint, closest })) =
+            mode.replay_interaction("/teams/1243")
+        else {
+            panic!("expected a cassette miss");
+        };
+        assert_eq!(endpoint, "/teams/1243");
+        assert_eq!(closest.as_deref(), Some("/teams/1234"));
+
+        assert!(matches!(
+            replay(Vec::new()).replay_interaction("/teams/1"),
+            Some(Err(error::Error::CassetteMiss { closest: None, .. }))
+        ));
+    }
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("/teams", "/teams/1"), 2);
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn recorded_requests_replay_without_the_server() {
+        use crate::{
+            mock::{tests::team, MockData, MockServer},
+            schema::TeamId,
+        };
+
+        let path =
+            std::env::temp_dir().join(format!("robotevents-cassette-{}.json", std::process::id()));
+        let server = MockServer::start(MockData {
+            teams: vec![team(1)],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let recorder = server
+            .client_builder("token")
+            .record_to(&path)
+            .build()
+            .unwrap();
+        let recorded = recorder.team(TeamId(1)).await.unwrap();
+        let missing = recorder.team(TeamId(2)).await.unwrap_err();
+        // Nothing is written until the cassette is saved.
+        assert!(!path.exists());
+        recorder.save_cassette().unwrap();
+
+        let cassette = Cassette::load(&path).unwrap();
+        let paths: Vec<_> = cassette.interactions.iter().map(|i| &i.path).collect();
+        assert_eq!(paths, ["/teams/1", "/teams/2"]);
+
+        // Replaying works after the server is gone, including recorded errors.
+        let base_url = server.base_url();
+        drop(server);
+        let replayer = crate::RobotEvents::builder("token")
+            .base_url(base_url)
+            .replay_from(&path)
+            .build()
+            .unwrap();
+
+        assert_eq!(replayer.team(TeamId(1)).await.unwrap(), recorded);
+        assert_eq!(
+            replayer.team(TeamId(2)).await.unwrap_err().status(),
+            missing.status()
+        );
+        assert!(matches!(
+            replayer.team(TeamId(3)).await,
+            Err(error::Error::CassetteMiss { .. })
+        ));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn cassettes_are_saved_when_the_client_is_dropped() {
+        use crate::mock::{MockData, MockServer};
+
+        let path = std::env::temp_dir().join(format!(
+            "robotevents-cassette-drop-{}.json",
+            std::process::id()
+        ));
+        let server = MockServer::start(MockData::default()).unwrap();
+
+        let recorder = server
+            .client_builder("token")
+            .record_to(&path)
+            .build()
+            .unwrap();
+        let clone = recorder.clone();
+        recorder.programs().await.unwrap();
+        drop(recorder);
+        assert!(!path.exists());
+
+        drop(clone);
+        assert_eq!(Cassette::load(&path).unwrap().interactions.len(), 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
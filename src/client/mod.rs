@@ -0,0 +1,2019 @@
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::{header::HeaderMap, StatusCode};
+
+use crate::query::{
+    DivisionMatchesQuery, DivisionRankingsQuery, EventAwardsQuery, EventSkillsQuery,
+    EventTeamsQuery, PaginatedQuery, QueryParams, SeasonEventsQuery, TeamAwardsQuery,
+    TeamEventsQuery, TeamMatchesQuery, TeamRankingsQuery, TeamSkillsQuery,
+};
+
+use super::{
+    query::{EventsQuery, SeasonsQuery, TeamsQuery},
+    schema::*,
+};
+use serde::de::DeserializeOwned;
+use std::{borrow::Cow, fmt::Display, path::PathBuf, sync::Arc, time::Duration};
+
+pub mod cache;
+pub mod cassette;
+pub mod rate_limit;
+pub mod retry;
+
+use cache::{CacheBackend, CachePolicy, ResponseCache};
+use cassette::{CassetteMode, RecordedResponse};
+use rate_limit::{RateLimiter, RateLimiterStats};
+use retry::RetryPolicy;
+
+pub mod error {
+    use reqwest::StatusCode;
+    use std::io;
+
+    /// The maximum number of characters of a response body kept in an [`Error`].
+    pub const MAX_BODY_LEN: usize = 512;
+
+    #[allow(clippy::enum_variant_names)]
+    #[derive(thiserror::Error, Debug)]
+    pub enum Error {
+        #[error("Reqwest error: {0}")]
+        ReqwestError(#[from] reqwest::Error),
+
+        /// The requested resource does not exist (`404 Not Found`).
+        #[error("Not found ({endpoint}): {body}")]
+        NotFound { endpoint: String, body: String },
+
+        /// The bearer token was missing, invalid or lacks access (`401`/`403`).
+        #[error(
+            "Unauthorized ({status}, {endpoint}), check that the bearer token is valid: {body}"
+        )]
+        Unauthorized {
+            endpoint: String,
+            status: StatusCode,
+            body: String,
+        },
+
+        /// The API kept responding with `429 Too Many Requests` until we ran out of attempts,
+        /// or asked to wait longer than the client's
+        /// [`RetryPolicy::max_delay`](super::retry::RetryPolicy::max_delay).
+        #[error("Rate limited after {attempts} attempts ({endpoint}): {body}")]
+        RateLimited {
+            endpoint: String,
+            attempts: u8,
+            /// How long the API asked to wait before trying again, if it said.
+            retry_after: Option<std::time::Duration>,
+            body: String,
+        },
+
+        /// RobotEvents failed to handle the request (`5xx`).
+        #[error("Server error ({status}, {endpoint}): {body}")]
+        ServerError {
+            endpoint: String,
+            status: StatusCode,
+            body: String,
+        },
+
+        /// Any other unsuccessful status code.
+        #[error("Unexpected status ({status}, {endpoint}): {body}")]
+        UnexpectedStatus {
+            endpoint: String,
+            status: StatusCode,
+            body: String,
+        },
+
+        /// The response body did not match the expected schema.
+        ///
+        /// `path` points to the field that failed to deserialize, such as
+        /// `data[17].location.coordinates.lat`.
+        #[error("Deserialization error at `{path}` ({endpoint}): {source}")]
+        DeserializeError {
+            endpoint: String,
+            path: String,
+            body: String,
+            #[source]
+            source: serde_json::Error,
+        },
+
+        /// The API returned a page URL (such as
+        /// [`PageMeta::next_page_url`](crate::schema::PageMeta::next_page_url)) that isn't an
+        /// API endpoint.
+        #[error("Page URL is not an API endpoint: {url}")]
+        InvalidPageUrl { url: String },
+
+        /// A replaying client was asked for an endpoint that isn't in its cassette.
+        #[error(
+            "No recorded response for {endpoint} in cassette{}",
+            closest.as_ref().map(|closest| format!(" (closest recorded path: {closest})")).unwrap_or_default()
+        )]
+        CassetteMiss {
+            endpoint: String,
+            closest: Option<String>,
+        },
+
+        /// A cassette file could not be read or written.
+        #[error("Cassette error: {0}")]
+        CassetteError(#[from] io::Error),
+
+        /// The runtime used by the [blocking](crate::blocking) client could not be started.
+        #[error("Failed to start the async runtime: {0}")]
+        RuntimeError(#[source] io::Error),
+    }
+
+    impl Error {
+        /// Creates the error matching an unsuccessful response status.
+        pub(crate) fn from_status(endpoint: &str, status: StatusCode, body: &str) -> Self {
+            let endpoint = endpoint.to_owned();
+            let body = truncate_body(body);
+
+            match status {
+                StatusCode::NOT_FOUND => Self::NotFound { endpoint, body },
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Unauthorized {
+                    endpoint,
+                    status,
+                    body,
+                },
+                status if status.is_server_error() => Self::ServerError {
+                    endpoint,
+                    status,
+                    body,
+                },
+                status => Self::UnexpectedStatus {
+                    endpoint,
+                    status,
+                    body,
+                },
+            }
+        }
+
+        /// The HTTP status code returned by the API, if the error was caused by one.
+        pub fn status(&self) -> Option<StatusCode> {
+            match self {
+                Self::ReqwestError(error) => error.status(),
+                Self::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+                Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+                Self::Unauthorized { status, .. }
+                | Self::ServerError { status, .. }
+                | Self::UnexpectedStatus { status, .. } => Some(*status),
+                Self::DeserializeError { .. }
+                | Self::InvalidPageUrl { .. }
+                | Self::CassetteMiss { .. }
+                | Self::CassetteError(_)
+                | Self::RuntimeError(_) => None,
+            }
+        }
+
+        /// The endpoint path that was being requested, if known.
+        pub fn endpoint(&self) -> Option<&str> {
+            match self {
+                Self::ReqwestError(_)
+                | Self::InvalidPageUrl { .. }
+                | Self::CassetteError(_)
+                | Self::RuntimeError(_) => None,
+                Self::NotFound { endpoint, .. }
+                | Self::Unauthorized { endpoint, .. }
+                | Self::RateLimited { endpoint, .. }
+                | Self::ServerError { endpoint, .. }
+                | Self::UnexpectedStatus { endpoint, .. }
+                | Self::DeserializeError { endpoint, .. }
+                | Self::CassetteMiss { endpoint, .. } => Some(endpoint),
+            }
+        }
+
+        /// The (truncated) response body returned by the API, if any.
+        pub fn body(&self) -> Option<&str> {
+            match self {
+                Self::ReqwestError(_)
+                | Self::InvalidPageUrl { .. }
+                | Self::CassetteMiss { .. }
+                | Self::CassetteError(_)
+                | Self::RuntimeError(_) => None,
+                Self::NotFound { body, .. }
+                | Self::Unauthorized { body, .. }
+                | Self::RateLimited { body, .. }
+                | Self::ServerError { body, .. }
+                | Self::UnexpectedStatus { body, .. }
+                | Self::DeserializeError { body, .. } => Some(body),
+            }
+        }
+    }
+
+    /// Shortens a response body to at most [`MAX_BODY_LEN`] characters.
+    pub(crate) fn truncate_body(body: &str) -> String {
+        match body.char_indices().nth(MAX_BODY_LEN) {
+            Some((end, _)) => format!("{}...", &body[..end]),
+            None => body.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RobotEvents {
+    pub bearer_token: String,
+    pub req_client: reqwest::Client,
+    pub base_url: String,
+    pub v1_base_url: String,
+    pub timeout: Duration,
+    pub retry_policy: RetryPolicy,
+    pub max_concurrent_requests: usize,
+    pub skip_invalid_records: bool,
+    cassette: Option<Arc<CassetteMode>>,
+    cache: Option<Arc<ResponseCache>>,
+    bypass_cache: bool,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+/// Which version of the RobotEvents API a request is made to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// The key a request is stored under in caches and cassettes. v1 endpoints are prefixed
+    /// so that they can't be confused with v2 endpoints of the same path.
+    fn key(self, endpoint: &str) -> Cow<'_, str> {
+        match self {
+            Self::V1 => Cow::Owned(format!("v1:{endpoint}")),
+            Self::V2 => Cow::Borrowed(endpoint),
+        }
+    }
+}
+
+pub const V1_API_BASE: &str = "https://www.robotevents.com/api";
+pub const V2_API_BASE: &str = "https://www.robotevents.com/api/v2";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+impl Default for RobotEvents {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl RobotEvents {
+    /// Creates a new RobotEvents API client.
+    ///
+    /// A bearer authentication token is required for requests to be made. This can
+    /// be obtained from RobotEvents by creating an account and requesting one.
+    ///
+    /// For more control over how the client is configured, see [`RobotEvents::builder`].
+    ///
+    /// # Examples
+    ///
+    /// Creating a client with a token stored as an enviornment variable:
+    ///
+    /// ```no_run
+    /// use robotevents::RobotEvents;
+    ///
+    /// let token = std::env::var("ROBOTEVENTS_TOKEN")?;
+    /// let client = RobotEvents::new(token);
+    /// # Ok::<(), std::env::VarError>(())
+    /// ```
+    pub fn new(bearer_token: impl AsRef<str>) -> Self {
+        Self {
+            bearer_token: bearer_token.as_ref().to_owned(),
+            req_client: reqwest::Client::new(),
+            base_url: V2_API_BASE.to_owned(),
+            v1_base_url: V1_API_BASE.to_owned(),
+            timeout: DEFAULT_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            skip_invalid_records: false,
+            cassette: None,
+            cache: None,
+            bypass_cache: false,
+            rate_limiter: None,
+        }
+    }
+
+    /// Creates a [`RobotEventsBuilder`] for configuring a new API client.
+    ///
+    /// # Examples
+    ///
+    /// Pointing a client at a local stand-in for the RobotEvents API:
+    ///
+    /// ```
+    /// use robotevents::RobotEvents;
+    /// use std::time::Duration;
+    ///
+    /// let client = RobotEvents::builder("token")
+    ///     .base_url("http://127.0.0.1:8080/api/v2")
+    ///     .timeout(Duration::from_secs(5))
+    ///     .max_attempts(1)
+    ///     .build()?;
+    /// # Ok::<(), robotevents::client::error::Error>(())
+    /// ```
+    pub fn builder(bearer_token: impl AsRef<str>) -> RobotEventsBuilder {
+        RobotEventsBuilder::new(bearer_token)
+    }
+
+    /// Returns a copy of the client that doesn't serve requests from its cache.
+    ///
+    /// Responses it receives are still stored in the cache, so this can also be used to
+    /// refresh a cached resource.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn run(client: robotevents::RobotEvents) -> Result<(), robotevents::client::error::Error> {
+    /// use robotevents::schema::{DivisionId, EventId};
+    ///
+    /// // Always fetch the latest rankings, even if they were requested a moment ago.
+    /// let rankings = client
+    ///     .no_cache()
+    ///     .event_division_rankings(EventId(51488), DivisionId(1), Default::default())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn no_cache(&self) -> Self {
+        Self {
+            bypass_cache: true,
+            ..self.clone()
+        }
+    }
+
+    /// Statistics on how long requests have waited for the client's [`RateLimiter`], if it
+    /// has one.
+    ///
+    /// The statistics are shared by every clone of the client.
+    pub fn rate_limiter_stats(&self) -> Option<RateLimiterStats> {
+        self.rate_limiter.as_ref().map(|limiter| limiter.stats())
+    }
+
+    /// Writes the requests recorded so far to the client's cassette file, if it was built with
+    /// [`RobotEventsBuilder::record_to`].
+    ///
+    /// The cassette is also saved when the last clone of the client is dropped, but errors
+    /// writing it can only be reported here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`error::Error::CassetteError`] if the file couldn't be written.
+    pub fn save_cassette(&self) -> Result<(), error::Error> {
+        match &self.cassette {
+            Some(cassette) => Ok(cassette.save()?),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes every response from the client's cache, if it has one.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.backend.clear();
+        }
+    }
+
+    /// Make a request to a [RobotEvents API v2](https://www.robotevents.com/api/v2) endpoint using the
+    /// client's bearer token.
+    ///
+    /// Failed requests are retried according to the client's [`RetryPolicy`].
+    ///
+    /// # Errors
+    ///
+    /// - [`error::Error::ReqwestError`] if the request couldn't be sent, after retrying
+    ///   connection errors and timeouts.
+    /// - [`error::Error::RateLimited`] if the API still responded with `429 Too Many Requests`
+    ///   on the last attempt, or asked to wait longer than [`RetryPolicy::max_delay`].
+    /// - [`error::Error::NotFound`], [`error::Error::Unauthorized`],
+    ///   [`error::Error::ServerError`] or [`error::Error::UnexpectedStatus`] for any other
+    ///   error status.
+    /// - [`error::Error::CassetteMiss`] or [`error::Error::CassetteError`] if the client is
+    ///   replaying or recording a cassette and it couldn't be used.
+    pub async fn request(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<reqwest::Response, error::Error> {
+        self.request_from(ApiVersion::V2, endpoint.as_ref()).await
+    }
+
+    /// Makes a request to an endpoint of either API version, going through the client's
+    /// cache, cassette, rate limiter and retry policy.
+    async fn request_from(
+        &self,
+        api: ApiVersion,
+        endpoint: &str,
+    ) -> Result<reqwest::Response, error::Error> {
+        let key = api.key(endpoint);
+        let cache = self.cache.as_ref();
+        if let Some(cached) = cache
+            .filter(|_| !self.bypass_cache)
+            .and_then(|cache| cache.get(&self.bearer_token, &key))
+        {
+            return Ok(cached.to_response(&self.url(api, endpoint)));
+        }
+
+        let max_attempts = self.retry_policy.max_attempts.max(1);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let retries_left = attempt < max_attempts;
+            let response = match self.send(api, endpoint).await {
+                Ok(response) => response,
+                Err(error::Error::ReqwestError(error))
+                    if retries_left && RetryPolicy::is_retryable_error(&error) =>
+                {
+                    futures_timer::Delay::new(self.retry_policy.backoff(attempt)).await;
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            let status = response.status();
+            if !status.is_client_error() && !status.is_server_error() {
+                let Some(cache) = cache else {
+                    return Ok(response);
+                };
+                let url = response.url().to_string();
+                let recorded = RecordedResponse::from_response(response).await?;
+                cache.insert(&self.bearer_token, &key, &recorded);
+                return Ok(recorded.to_response(&url));
+            }
+
+            let retry_after = retry::retry_after(response.headers());
+            if retries_left && RetryPolicy::is_retryable_status(status) {
+                // Wait as long as the API asked, or back off if it didn't say. Asking for
+                // longer than the policy allows fails the request straight away.
+                let delay = retry_after.unwrap_or_else(|| self.retry_policy.backoff(attempt));
+                if delay <= self.retry_policy.max_delay {
+                    futures_timer::Delay::new(delay).await;
+                    continue;
+                }
+            }
+
+            let body = response.text().await.unwrap_or_default();
+            return Err(if status == StatusCode::TOO_MANY_REQUESTS {
+                error::Error::RateLimited {
+                    endpoint: endpoint.to_owned(),
+                    attempts: attempt,
+                    retry_after,
+                    body: error::truncate_body(&body),
+                }
+            } else {
+                error::Error::from_status(endpoint, status, &body)
+            });
+        }
+    }
+
+    /// Sends a single request to an endpoint of either API version, or serves it from the
+    /// cassette when recording or replaying.
+    async fn send(
+        &self,
+        api: ApiVersion,
+        endpoint: &str,
+    ) -> Result<reqwest::Response, error::Error> {
+        let url = self.url(api, endpoint);
+        let key = api.key(endpoint);
+
+        if let Some(recorded) = self
+            .cassette
+            .as_ref()
+            .and_then(|cassette| cassette.replay_interaction(&key))
+        {
+            return Ok(recorded?.to_response(&url));
+        }
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let response = self
+            .req_client
+            .get(&url)
+            .bearer_auth(&self.bearer_token)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+
+        match &self.cassette {
+            Some(cassette) => {
+                let recorded = RecordedResponse::from_response(response).await?;
+                cassette.record_interaction(&key, &recorded);
+                Ok(recorded.to_response(&url))
+            }
+            None => Ok(response),
+        }
+    }
+
+    /// Make a request to a v2 API endpoint and deserialize the JSON response body.
+    ///
+    /// If the body doesn't match `T`, the returned [`error::Error::DeserializeError`]
+    /// includes the path to the offending field (e.g. `data[17].location.coordinates.lat`).
+    pub async fn request_json<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<T, error::Error> {
+        let endpoint = endpoint.as_ref();
+        let body = self.request(endpoint).await?.text().await?;
+        Self::decode_json(endpoint, &body)
+    }
+
+    /// Deserializes a JSON response body, reporting the path to the field that failed.
+    fn decode_json<T: DeserializeOwned>(endpoint: &str, body: &str) -> Result<T, error::Error> {
+        serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(body)).map_err(
+            |error| error::Error::DeserializeError {
+                endpoint: endpoint.to_owned(),
+                path: error.path().to_string(),
+                body: error::truncate_body(body),
+                source: error.into_inner(),
+            },
+        )
+    }
+
+    /// Make a request to a paginated v2 API endpoint and deserialize the page.
+    ///
+    /// When [`Self::skip_invalid_records`] is enabled, records in `data` that fail to
+    /// deserialize are moved to [`PaginatedResponse::skipped`] instead of failing the
+    /// whole page.
+    pub async fn request_page<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<PaginatedResponse<T>, error::Error> {
+        if !self.skip_invalid_records {
+            return self.request_json(endpoint).await;
+        }
+
+        let raw_page: PaginatedResponse<serde_json::Value> = self.request_json(endpoint).await?;
+        let mut page = PaginatedResponse {
+            meta: raw_page.meta,
+            data: Vec::with_capacity(raw_page.data.len()),
+            skipped: Vec::new(),
+        };
+
+        for (index, raw) in raw_page.data.into_iter().enumerate() {
+            match serde_path_to_error::deserialize(&raw) {
+                Ok(record) => page.data.push(record),
+                Err(error) => {
+                    let field = error.path().to_string();
+                    page.skipped.push(SkippedRecord {
+                        index,
+                        path: match field.as_str() {
+                            "." => format!("data[{index}]"),
+                            field => format!("data[{index}].{field}"),
+                        },
+                        error: error.into_inner().to_string(),
+                        raw,
+                    });
+                }
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Lazily walks every page of a paginated v2 API endpoint, yielding each item in order.
+    ///
+    /// The first page is requested from `endpoint` once the stream is first polled. Each
+    /// following page is requested using the previous page's [`PageMeta::next_page_url`],
+    /// so consumers that stop early (for example through [`StreamExt::take`]) never fetch
+    /// pages they don't need. The stream ends after the first error.
+    ///
+    /// [`StreamExt::take`]: futures::StreamExt::take
+    pub fn paginate<T: DeserializeOwned + 'static>(
+        &self,
+        endpoint: impl Into<String>,
+    ) -> impl Stream<Item = Result<T, error::Error>> {
+        let client = self.clone();
+
+        stream::try_unfold(Some(endpoint.into()), move |endpoint| {
+            let client = client.clone();
+            async move {
+                let Some(endpoint) = endpoint else {
+                    return Ok::<_, error::Error>(None);
+                };
+
+                let page = client.request_page::<T>(endpoint).await?;
+                let next_endpoint = page
+                    .meta
+                    .next_page_url
+                    .map(|url| client.endpoint_from_url(&url))
+                    .transpose()?;
+
+                Ok(Some((
+                    stream::iter(page.data.into_iter().map(Ok)),
+                    next_endpoint,
+                )))
+            }
+        })
+        .try_flatten()
+    }
+
+    /// Fetches every page of a paginated v2 API endpoint, returning all items in page order.
+    ///
+    /// The first page is requested to find out how many pages there are, after which the
+    /// remaining pages are requested concurrently. At most
+    /// [`Self::max_concurrent_requests`] pages are in flight at once, so large queries don't
+    /// immediately run into the API's rate limit.
+    pub async fn all_pages<T, Q>(
+        &self,
+        endpoint: impl AsRef<str>,
+        query: Q,
+    ) -> Result<Vec<T>, error::Error>
+    where
+        T: DeserializeOwned,
+        Q: PaginatedQuery + Display + Clone,
+    {
+        let endpoint = endpoint.as_ref();
+
+        // Get the first page
+        let first_page = self.request_page::<T>(format!("{endpoint}{query}")).await?;
+        let mut out = first_page.data;
+
+        // Fetch the remaining pages, keeping at most `max_concurrent_requests` in flight.
+        let mut pages = stream::iter(2..=first_page.meta.last_page)
+            .map(|page| {
+                let query = query.clone().page(page);
+                async move {
+                    let page = self.request_page::<T>(format!("{endpoint}{query}")).await?;
+                    Ok::<_, error::Error>(page)
+                }
+            })
+            .buffered(self.max_concurrent_requests.max(1));
+
+        while let Some(mut page) = pages.try_next().await? {
+            out.append(&mut page.data);
+        }
+
+        Ok(out)
+    }
+
+    /// Make a request to a page URL returned by the API (such as
+    /// [`PageMeta::next_page_url`]) and deserialize the page.
+    pub(crate) async fn request_page_url<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<PaginatedResponse<T>, error::Error> {
+        self.request_page(self.endpoint_from_url(url)?).await
+    }
+
+    /// Converts an absolute URL returned by the API (such as [`PageMeta::next_page_url`])
+    /// into an endpoint path relative to [`Self::base_url`].
+    ///
+    /// The API names its own host in these URLs, so when [`Self::base_url`] points somewhere
+    /// else (such as a proxy) the endpoint is taken from the URL's path under `/api/v2`.
+    pub(crate) fn endpoint_from_url(&self, url: &str) -> Result<String, error::Error> {
+        let invalid = || error::Error::InvalidPageUrl {
+            url: url.to_owned(),
+        };
+        let under = |path: &str, base: &str| {
+            path.strip_prefix(base.trim_end_matches('/'))
+                .filter(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+                .map(str::to_owned)
+        };
+
+        if let Some(endpoint) = under(url, &self.base_url) {
+            return Ok(endpoint);
+        }
+
+        let parsed = reqwest::Url::parse(url).map_err(|_| invalid())?;
+        let mut endpoint = [self.base_url.as_str(), V2_API_BASE]
+            .into_iter()
+            .filter_map(|base| reqwest::Url::parse(base).ok())
+            .find_map(|base| under(parsed.path(), base.path()))
+            .ok_or_else(invalid)?;
+        if let Some(query) = parsed.query() {
+            endpoint.push('?');
+            endpoint.push_str(query);
+        }
+        Ok(endpoint)
+    }
+
+    /// The full URL of an endpoint.
+    fn url(&self, api: ApiVersion, endpoint: &str) -> String {
+        match api {
+            ApiVersion::V1 => format!("{}{endpoint}", self.v1_base_url),
+            ApiVersion::V2 => format!("{}{endpoint}", self.base_url),
+        }
+    }
+
+    /// Make a request to a RobotEvents API v1 endpoint.
+    ///
+    /// Requests are retried, rate limited, cached and recorded the same way as
+    /// [`Self::request`].
+    pub async fn request_api_v1(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<reqwest::Response, error::Error> {
+        self.request_from(ApiVersion::V1, endpoint.as_ref()).await
+    }
+
+    /// Make a request to a RobotEvents API v1 endpoint and deserialize the JSON response body.
+    pub async fn request_api_v1_json<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+    ) -> Result<T, error::Error> {
+        let endpoint = endpoint.as_ref();
+        let body = self.request_api_v1(endpoint).await?.text().await?;
+        Self::decode_json(endpoint, &body)
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Team-related endpoint methods
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Get a paginated list of [`Team`]s from RobotEvents.
+    ///
+    /// Team listings can be queryed using a [`TeamsQuery`] search.
+    pub async fn teams(&self, query: TeamsQuery) -> Result<PaginatedResponse<Team>, error::Error> {
+        self.request_page(format!("/teams{query}")).await
+    }
+
+    /// Get a stream of every [`Team`] matching a [`TeamsQuery`] search.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn teams_stream(
+        &self,
+        query: TeamsQuery,
+    ) -> impl Stream<Item = Result<Team, error::Error>> {
+        self.paginate(format!("/teams{query}"))
+    }
+
+    /// Get a non-paginated list of [`Team`]s from RobotEvents.
+    ///
+    /// Team listings can be queryed using a [`TeamsQuery`] search.
+    pub async fn all_teams(&self, query: TeamsQuery) -> Result<Vec<Team>, error::Error> {
+        self.all_pages("/teams", query).await
+    }
+
+    /// Get a specific RobotEvents [`Team`] by ID.
+    pub async fn team(&self, team_id: TeamId) -> Result<Team, error::Error> {
+        self.request_json(format!("/teams/{team_id}")).await
+    }
+
+    /// Finds a [`Team`] by its number (such as `1234A`) in a given program.
+    ///
+    /// Team numbers are only unique within a program, so the same number may belong to
+    /// different teams in, for example, V5RC and VIQRC. Returns `None` if the program has no
+    /// team with that number, including when `number` isn't a valid [`TeamNumber`] for the
+    /// program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn run(client: robotevents::RobotEvents) -> Result<(), robotevents::client::error::Error> {
+    /// use robotevents::schema::ProgramId;
+    ///
+    /// if let Some(team) = client.team_by_number("1082R", ProgramId(1)).await? {
+    ///     println!("{} is team {}", team.number, team.id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn team_by_number(
+        &self,
+        number: impl AsRef<str>,
+        program: ProgramId,
+    ) -> Result<Option<Team>, error::Error> {
+        let Ok(number) = TeamNumber::parse_for(number, program) else {
+            return Ok(None);
+        };
+        let teams = self
+            .teams(TeamsQuery::new().number(number.clone()).program(program))
+            .await?;
+
+        Ok(teams.data.into_iter().find(|team| {
+            team.program.id == program && team.number.eq_ignore_ascii_case(number.as_str())
+        }))
+    }
+
+    /// Gets a List of [`Event`]s that a given Team ID has attended.
+    pub async fn team_events(
+        &self,
+        team_id: TeamId,
+        query: TeamEventsQuery,
+    ) -> Result<PaginatedResponse<Event>, error::Error> {
+        self.request_page(format!("/teams/{team_id}/events{query}"))
+            .await
+    }
+
+    /// Get a stream of every [`Event`] that a given Team ID has attended.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn team_events_stream(
+        &self,
+        team_id: TeamId,
+        query: TeamEventsQuery,
+    ) -> impl Stream<Item = Result<Event, error::Error>> {
+        self.paginate(format!("/teams/{team_id}/events{query}"))
+    }
+
+    /// Gets a non-paginated list of [`Event`]s that a given Team ID has attended.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_team_events(
+        &self,
+        team_id: TeamId,
+        query: TeamEventsQuery,
+    ) -> Result<Vec<Event>, error::Error> {
+        self.all_pages(format!("/teams/{team_id}/events"), query)
+            .await
+    }
+
+    /// Gets a List of [`Match`]es that a given Team ID has played in.
+    pub async fn team_matches(
+        &self,
+        team_id: TeamId,
+        query: TeamMatchesQuery,
+    ) -> Result<PaginatedResponse<Match>, error::Error> {
+        self.request_page(format!("/teams/{team_id}/matches{query}"))
+            .await
+    }
+
+    /// Get a stream of every [`Match`] that a given Team ID has played in.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn team_matches_stream(
+        &self,
+        team_id: TeamId,
+        query: TeamMatchesQuery,
+    ) -> impl Stream<Item = Result<Match, error::Error>> {
+        self.paginate(format!("/teams/{team_id}/matches{query}"))
+    }
+
+    /// Gets a non-paginated list of [`Match`]es that a given Team ID has played in.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_team_matches(
+        &self,
+        team_id: TeamId,
+        query: TeamMatchesQuery,
+    ) -> Result<Vec<Match>, error::Error> {
+        self.all_pages(format!("/teams/{team_id}/matches"), query)
+            .await
+    }
+
+    /// Gets a List of [`Ranking`]s that a given Team ID has played in.
+    pub async fn team_rankings(
+        &self,
+        team_id: TeamId,
+        query: TeamRankingsQuery,
+    ) -> Result<PaginatedResponse<Ranking>, error::Error> {
+        self.request_page(format!("/teams/{team_id}/rankings{query}"))
+            .await
+    }
+
+    /// Get a stream of every [`Ranking`] that a given Team ID has received.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn team_rankings_stream(
+        &self,
+        team_id: TeamId,
+        query: TeamRankingsQuery,
+    ) -> impl Stream<Item = Result<Ranking, error::Error>> {
+        self.paginate(format!("/teams/{team_id}/rankings{query}"))
+    }
+
+    /// Gets a non-paginated list of [`Ranking`]s that a given Team ID has received.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_team_rankings(
+        &self,
+        team_id: TeamId,
+        query: TeamRankingsQuery,
+    ) -> Result<Vec<Ranking>, error::Error> {
+        self.all_pages(format!("/teams/{team_id}/rankings"), query)
+            .await
+    }
+
+    /// Gets a List of [`Skill`]s runs that a given Team ID has performed.
+    pub async fn team_skills(
+        &self,
+        team_id: TeamId,
+        query: TeamSkillsQuery,
+    ) -> Result<PaginatedResponse<Skill>, error::Error> {
+        self.request_page(format!("/teams/{team_id}/skills{query}"))
+            .await
+    }
+
+    /// Get a stream of every [`Skill`] run that a given Team ID has performed.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn team_skills_stream(
+        &self,
+        team_id: TeamId,
+        query: TeamSkillsQuery,
+    ) -> impl Stream<Item = Result<Skill, error::Error>> {
+        self.paginate(format!("/teams/{team_id}/skills{query}"))
+    }
+
+    /// Gets a non-paginated list of [`Skill`]s runs that a given Team ID has performed.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_team_skills(
+        &self,
+        team_id: TeamId,
+        query: TeamSkillsQuery,
+    ) -> Result<Vec<Skill>, error::Error> {
+        self.all_pages(format!("/teams/{team_id}/skills"), query)
+            .await
+    }
+
+    /// Gets a List of [`Award`]s that a given Team ID has received.
+    pub async fn team_awards(
+        &self,
+        team_id: TeamId,
+        query: TeamAwardsQuery,
+    ) -> Result<PaginatedResponse<Award>, error::Error> {
+        self.request_page(format!("/teams/{team_id}/awards{query}"))
+            .await
+    }
+
+    /// Get a stream of every [`Award`] that a given Team ID has received.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn team_awards_stream(
+        &self,
+        team_id: TeamId,
+        query: TeamAwardsQuery,
+    ) -> impl Stream<Item = Result<Award, error::Error>> {
+        self.paginate(format!("/teams/{team_id}/awards{query}"))
+    }
+
+    /// Gets a non-paginated list of [`Award`]s that a given Team ID has received.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_team_awards(
+        &self,
+        team_id: TeamId,
+        query: TeamAwardsQuery,
+    ) -> Result<Vec<Award>, error::Error> {
+        self.all_pages(format!("/teams/{team_id}/awards"), query)
+            .await
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Season-related endpoint methods
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Get a paginated list of [`Season`]s from RobotEvents.
+    ///
+    /// Season listings can be queryed using a [`SeasonQuery`] search.
+    pub async fn seasons(
+        &self,
+        query: SeasonsQuery,
+    ) -> Result<PaginatedResponse<Season>, error::Error> {
+        self.request_page(format!("/seasons{query}")).await
+    }
+
+    /// Get a stream of every [`Season`] matching a [`SeasonsQuery`] search.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn seasons_stream(
+        &self,
+        query: SeasonsQuery,
+    ) -> impl Stream<Item = Result<Season, error::Error>> {
+        self.paginate(format!("/seasons{query}"))
+    }
+
+    /// Get a non-paginated list of [`Season`]s from RobotEvents.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_seasons(&self, query: SeasonsQuery) -> Result<Vec<Season>, error::Error> {
+        self.all_pages("/seasons", query).await
+    }
+
+    /// Get a specific RobotEvents [`Season`] by ID.
+    pub async fn season(&self, season_id: SeasonId) -> Result<Season, error::Error> {
+        self.request_json(format!("/seasons/{season_id}")).await
+    }
+
+    /// Gets a List of Events for a given Season.
+    pub async fn season_events(
+        &self,
+        season_id: SeasonId,
+        query: SeasonEventsQuery,
+    ) -> Result<PaginatedResponse<Event>, error::Error> {
+        self.request_page(format!("/seasons/{season_id}/events{query}"))
+            .await
+    }
+
+    /// Get a stream of every [`Event`] in a given Season.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn season_events_stream(
+        &self,
+        season_id: SeasonId,
+        query: SeasonEventsQuery,
+    ) -> impl Stream<Item = Result<Event, error::Error>> {
+        self.paginate(format!("/seasons/{season_id}/events{query}"))
+    }
+
+    /// Gets a non-paginated list of [`Event`]s for a given Season.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_season_events(
+        &self,
+        season_id: SeasonId,
+        query: SeasonEventsQuery,
+    ) -> Result<Vec<Event>, error::Error> {
+        self.all_pages(format!("/seasons/{season_id}/events"), query)
+            .await
+    }
+
+    /// Gets the world skills standings for a season and grade level from the v1 API.
+    ///
+    /// With `post_season` set, the standings after the season's qualification deadline are
+    /// returned instead.
+    pub async fn world_skills(
+        &self,
+        season_id: SeasonId,
+        grade: Grade,
+        post_season: bool,
+    ) -> Result<Vec<v1::SkillsStanding>, error::Error> {
+        let mut query = QueryParams::new();
+        query.set("post_season", u8::from(post_season));
+        query.set("grade_level", grade);
+
+        self.request_api_v1_json(format!("/seasons/{season_id}/skills{query}"))
+            .await
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Program-related endpoint methods
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Get a paginated list of all programs from RobotEvents.
+    pub async fn programs(&self) -> Result<PaginatedResponse<IdInfo<ProgramId>>, error::Error> {
+        self.request_page("/programs").await
+    }
+
+    /// Get a stream of every program on RobotEvents.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn programs_stream(&self) -> impl Stream<Item = Result<IdInfo<ProgramId>, error::Error>> {
+        self.paginate("/programs")
+    }
+
+    /// Get a non-paginated list of all programs from RobotEvents.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_programs(&self) -> Result<Vec<IdInfo<ProgramId>>, error::Error> {
+        self.all_pages("/programs", QueryParams::new()).await
+    }
+
+    /// Get a specific RobotEvents program by ID.
+    pub async fn program(&self, program_id: ProgramId) -> Result<IdInfo<ProgramId>, error::Error> {
+        self.request_json(format!("/programs/{program_id}")).await
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Event-related endpoint methods
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Get a paginated list of [`Event`]s from RobotEvents.
+    ///
+    /// Event listings can be queryed using an [`EventQuery`] search.
+    pub async fn events(
+        &self,
+        query: EventsQuery,
+    ) -> Result<PaginatedResponse<Event>, error::Error> {
+        self.request_page(format!("/events{query}")).await
+    }
+
+    /// Get a stream of every [`Event`] matching an [`EventsQuery`] search.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn events_stream(
+        &self,
+        query: EventsQuery,
+    ) -> impl Stream<Item = Result<Event, error::Error>> {
+        self.paginate(format!("/events{query}"))
+    }
+
+    /// Get a non-paginated list of [`Event`]s from RobotEvents.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_events(&self, query: EventsQuery) -> Result<Vec<Event>, error::Error> {
+        self.all_pages("/events", query).await
+    }
+
+    /// Get a specific RobotEvents event by ID.
+    pub async fn event(&self, event_id: EventId) -> Result<Event, error::Error> {
+        self.request_json(format!("/events/{event_id}")).await
+    }
+
+    /// Finds an [`Event`] by its SKU, such as `RE-V5RC-24-1234`.
+    ///
+    /// Returns `None` if there is no event with that SKU, including when `sku` isn't a valid
+    /// [`EventSku`].
+    pub async fn event_by_sku(&self, sku: impl AsRef<str>) -> Result<Option<Event>, error::Error> {
+        let Ok(sku) = EventSku::new(sku) else {
+            return Ok(None);
+        };
+        let events = self.events(EventsQuery::new().sku(sku.clone())).await?;
+
+        Ok(events
+            .data
+            .into_iter()
+            .find(|event| event.sku.eq_ignore_ascii_case(sku.as_str())))
+    }
+
+    /// Get a paginated list of teams attending an event.
+    pub async fn event_teams(
+        &self,
+        event_id: EventId,
+        query: EventTeamsQuery,
+    ) -> Result<PaginatedResponse<Team>, error::Error> {
+        self.request_page(format!("/events/{event_id}/teams{query}"))
+            .await
+    }
+
+    /// Get a stream of every team attending an event.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn event_teams_stream(
+        &self,
+        event_id: EventId,
+        query: EventTeamsQuery,
+    ) -> impl Stream<Item = Result<Team, error::Error>> {
+        self.paginate(format!("/events/{event_id}/teams{query}"))
+    }
+
+    /// Get a non-paginated list of teams attending an event.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_event_teams(
+        &self,
+        event_id: EventId,
+        query: EventTeamsQuery,
+    ) -> Result<Vec<Team>, error::Error> {
+        self.all_pages(format!("/events/{event_id}/teams"), query)
+            .await
+    }
+
+    /// Get a paginated list of skills runs at an event.
+    pub async fn event_skills(
+        &self,
+        event_id: EventId,
+        query: EventSkillsQuery,
+    ) -> Result<PaginatedResponse<Skill>, error::Error> {
+        self.request_page(format!("/events/{event_id}/skills{query}"))
+            .await
+    }
+
+    /// Get a stream of every skills run at an event.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn event_skills_stream(
+        &self,
+        event_id: EventId,
+        query: EventSkillsQuery,
+    ) -> impl Stream<Item = Result<Skill, error::Error>> {
+        self.paginate(format!("/events/{event_id}/skills{query}"))
+    }
+
+    /// Get a non-paginated list of skills runs at an event.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_event_skills(
+        &self,
+        event_id: EventId,
+        query: EventSkillsQuery,
+    ) -> Result<Vec<Skill>, error::Error> {
+        self.all_pages(format!("/events/{event_id}/skills"), query)
+            .await
+    }
+
+    /// Get a paginated list of skills runs at an event.
+    pub async fn event_awards(
+        &self,
+        event_id: EventId,
+        query: EventAwardsQuery,
+    ) -> Result<PaginatedResponse<Award>, error::Error> {
+        self.request_page(format!("/events/{event_id}/awards{query}"))
+            .await
+    }
+
+    /// Get a stream of every award at an event.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn event_awards_stream(
+        &self,
+        event_id: EventId,
+        query: EventAwardsQuery,
+    ) -> impl Stream<Item = Result<Award, error::Error>> {
+        self.paginate(format!("/events/{event_id}/awards{query}"))
+    }
+
+    /// Get a non-paginated list of awards at an event.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_event_awards(
+        &self,
+        event_id: EventId,
+        query: EventAwardsQuery,
+    ) -> Result<Vec<Award>, error::Error> {
+        self.all_pages(format!("/events/{event_id}/awards"), query)
+            .await
+    }
+
+    /// Gets a List of Matches for a single Division of an Event.
+    pub async fn event_division_matches(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionMatchesQuery,
+    ) -> Result<PaginatedResponse<Match>, error::Error> {
+        self.request_page(format!(
+            "/events/{event_id}/divisions/{division_id}/matches{query}"
+        ))
+        .await
+    }
+
+    /// Get a stream of every Match for a single Division of an Event.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn event_division_matches_stream(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionMatchesQuery,
+    ) -> impl Stream<Item = Result<Match, error::Error>> {
+        self.paginate(format!(
+            "/events/{event_id}/divisions/{division_id}/matches{query}"
+        ))
+    }
+
+    /// Gets a non-paginated list of Matches for a single Division of an Event.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_event_division_matches(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionMatchesQuery,
+    ) -> Result<Vec<Match>, error::Error> {
+        self.all_pages(
+            format!("/events/{event_id}/divisions/{division_id}/matches"),
+            query,
+        )
+        .await
+    }
+
+    /// Gets a List of Finalist Rankings for a single Division of an Event.
+    pub async fn event_division_finalist_rankings(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionRankingsQuery,
+    ) -> Result<PaginatedResponse<Ranking>, error::Error> {
+        self.request_page(format!(
+            "/events/{event_id}/divisions/{division_id}/finalistRankings{query}"
+        ))
+        .await
+    }
+
+    /// Get a stream of every Finalist Ranking for a single Division of an Event.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn event_division_finalist_rankings_stream(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionRankingsQuery,
+    ) -> impl Stream<Item = Result<Ranking, error::Error>> {
+        self.paginate(format!(
+            "/events/{event_id}/divisions/{division_id}/finalistRankings{query}"
+        ))
+    }
+
+    /// Gets a non-paginated list of Finalist Rankings for a single Division of an Event.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_event_division_finalist_rankings(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionRankingsQuery,
+    ) -> Result<Vec<Ranking>, error::Error> {
+        self.all_pages(
+            format!("/events/{event_id}/divisions/{division_id}/finalistRankings"),
+            query,
+        )
+        .await
+    }
+
+    /// Gets a List of Rankings for a single Division of an Event.
+    pub async fn event_division_rankings(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionRankingsQuery,
+    ) -> Result<PaginatedResponse<Ranking>, error::Error> {
+        self.request_page(format!(
+            "/events/{event_id}/divisions/{division_id}/rankings{query}"
+        ))
+        .await
+    }
+
+    /// Get a stream of every Ranking for a single Division of an Event.
+    ///
+    /// Pages are fetched lazily as the stream is polled. See [`Self::paginate`].
+    pub fn event_division_rankings_stream(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionRankingsQuery,
+    ) -> impl Stream<Item = Result<Ranking, error::Error>> {
+        self.paginate(format!(
+            "/events/{event_id}/divisions/{division_id}/rankings{query}"
+        ))
+    }
+
+    /// Gets a non-paginated list of Rankings for a single Division of an Event.
+    ///
+    /// See [`Self::all_pages`].
+    pub async fn all_event_division_rankings(
+        &self,
+        event_id: EventId,
+        division_id: DivisionId,
+        query: DivisionRankingsQuery,
+    ) -> Result<Vec<Ranking>, error::Error> {
+        self.all_pages(
+            format!("/events/{event_id}/divisions/{division_id}/rankings"),
+            query,
+        )
+        .await
+    }
+}
+
+/// A builder for configuring a [`RobotEvents`] client.
+///
+/// Created with [`RobotEvents::builder`].
+#[derive(Debug, Clone)]
+pub struct RobotEventsBuilder {
+    bearer_token: String,
+    base_url: String,
+    v1_base_url: String,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    max_concurrent_requests: usize,
+    skip_invalid_records: bool,
+    record_to: Option<PathBuf>,
+    replay_from: Option<PathBuf>,
+    cache: Option<Arc<dyn CacheBackend>>,
+    cache_policy: CachePolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    req_client: Option<reqwest::Client>,
+}
+
+impl RobotEventsBuilder {
+    pub fn new(bearer_token: impl AsRef<str>) -> Self {
+        Self {
+            bearer_token: bearer_token.as_ref().to_owned(),
+            base_url: V2_API_BASE.to_owned(),
+            v1_base_url: V1_API_BASE.to_owned(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            skip_invalid_records: false,
+            record_to: None,
+            replay_from: None,
+            cache: None,
+            cache_policy: CachePolicy::default(),
+            rate_limiter: None,
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            req_client: None,
+        }
+    }
+
+    /// Sets the base URL that v2 API endpoints are requested from.
+    ///
+    /// Defaults to [`V2_API_BASE`].
+    pub fn base_url(mut self, base_url: impl AsRef<str>) -> Self {
+        self.base_url = base_url.as_ref().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Sets the base URL that v1 API endpoints are requested from.
+    ///
+    /// Defaults to [`V1_API_BASE`].
+    pub fn v1_base_url(mut self, v1_base_url: impl AsRef<str>) -> Self {
+        self.v1_base_url = v1_base_url.as_ref().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Sets the timeout applied to each individual request. Defaults to 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection to the API.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets how many times a request is attempted before giving up. Defaults to 5.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u8) -> Self {
+        self.retry_policy.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets how failed requests are retried. Defaults to [`RetryPolicy::default`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets how many pages collectors such as [`RobotEvents::all_teams`] may request
+    /// at once. Defaults to 4.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
+    }
+
+    /// Skips records in paginated responses that fail to deserialize rather than failing
+    /// the whole page. Defaults to `false`.
+    ///
+    /// Skipped records are listed in [`PaginatedResponse::skipped`]. Streams and
+    /// collectors such as [`RobotEvents::all_teams`] silently leave them out.
+    pub fn skip_invalid_records(mut self, skip_invalid_records: bool) -> Self {
+        self.skip_invalid_records = skip_invalid_records;
+        self
+    }
+
+    /// Records every request and the response it received to a [cassette](cassette::Cassette)
+    /// file at `path`, overwriting any existing file.
+    ///
+    /// Recorded requests are kept in memory until [`RobotEvents::save_cassette`] is called or
+    /// the last clone of the client is dropped.
+    pub fn record_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_to = Some(path.into());
+        self.replay_from = None;
+        self
+    }
+
+    /// Serves requests from the [cassette](cassette::Cassette) file at `path` instead of the
+    /// network.
+    ///
+    /// Requests for endpoints that weren't recorded fail with
+    /// [`error::Error::CassetteMiss`].
+    pub fn replay_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay_from = Some(path.into());
+        self.record_to = None;
+        self
+    }
+
+    /// Caches successful responses in `backend`, such as a [`cache::MemoryCache`] or
+    /// [`cache::DiskCache`].
+    ///
+    /// See [`RobotEvents::no_cache`] for skipping the cache on individual requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use robotevents::{
+    ///     client::cache::{CachePolicy, MemoryCache, ResourceKind},
+    ///     RobotEvents,
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// let client = RobotEvents::builder("token")
+    ///     .cache(MemoryCache::new(1000))
+    ///     .cache_policy(CachePolicy::default().ttl(ResourceKind::Rankings, Duration::from_secs(30)))
+    ///     .build()?;
+    /// # Ok::<(), robotevents::client::error::Error>(())
+    /// ```
+    pub fn cache(mut self, backend: impl CacheBackend + 'static) -> Self {
+        self.cache = Some(Arc::new(backend));
+        self
+    }
+
+    /// Sets how long cached responses stay fresh. Defaults to [`CachePolicy::default`].
+    pub fn cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = cache_policy;
+        self
+    }
+
+    /// Limits how quickly requests are sent, so that the API's rate limit is never reached.
+    ///
+    /// The limiter is shared by every clone of the built client. Passing an
+    /// `Arc<RateLimiter>` shares it with other clients too.
+    pub fn rate_limiter(mut self, rate_limiter: impl Into<Arc<RateLimiter>>) -> Self {
+        self.rate_limiter = Some(rate_limiter.into());
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets headers that are sent with every request.
+    pub fn default_headers(mut self, default_headers: HeaderMap) -> Self {
+        self.default_headers = default_headers;
+        self
+    }
+
+    /// Uses a pre-built [`reqwest::Client`] to make requests.
+    ///
+    /// When a client is provided, [`Self::user_agent`], [`Self::default_headers`] and
+    /// [`Self::connect_timeout`] are ignored, since they must be configured on the
+    /// [`reqwest::Client`] itself.
+    pub fn client(mut self, req_client: reqwest::Client) -> Self {
+        self.req_client = Some(req_client);
+        self
+    }
+
+    /// Builds the [`RobotEvents`] client.
+    pub fn build(self) -> Result<RobotEvents, error::Error> {
+        let req_client = match self.req_client {
+            Some(req_client) => req_client,
+            None => {
+                let mut builder = reqwest::Client::builder().default_headers(self.default_headers);
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                builder.build()?
+            }
+        };
+
+        let cassette = match (self.record_to, self.replay_from) {
+            (_, Some(path)) => Some(Arc::new(CassetteMode::replay(&path)?)),
+            (Some(path), None) => Some(Arc::new(CassetteMode::record(path))),
+            (None, None) => None,
+        };
+
+        Ok(RobotEvents {
+            bearer_token: self.bearer_token,
+            req_client,
+            base_url: self.base_url,
+            v1_base_url: self.v1_base_url,
+            timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            max_concurrent_requests: self.max_concurrent_requests,
+            skip_invalid_records: self.skip_invalid_records,
+            cassette,
+            cache: self.cache.map(|backend| {
+                Arc::new(ResponseCache {
+                    backend,
+                    policy: self.cache_policy,
+                })
+            }),
+            bypass_cache: false,
+            rate_limiter: self.rate_limiter,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock::{
+        tests::{client, event, team},
+        Fault, MockData, MockServer,
+    };
+
+    fn server() -> MockServer {
+        MockServer::start(MockData {
+            teams: vec![team(1)],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn too_many_requests(retry_after: &str) -> Fault {
+        Fault::TooManyRequests {
+            retry_after: Some(retry_after.to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limited_then_success() {
+        let server = server();
+        server.push_fault(too_many_requests("0"));
+
+        let team = client(&server).team(TeamId(1)).await.unwrap();
+
+        assert_eq!(team.id, TeamId(1));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn rate_limited_until_attempts_run_out() {
+        let server = server();
+        for _ in 0..5 {
+            server.push_fault(too_many_requests("0"));
+        }
+
+        let error = client(&server).team(TeamId(1)).await.unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                error::Error::RateLimited {
+                    attempts: 5,
+                    retry_after: Some(Duration::ZERO),
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn long_retry_after_fails_straight_away() {
+        let server = server();
+        server.push_fault(too_many_requests("3600"));
+
+        let error = client(&server).team(TeamId(1)).await.unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                error::Error::RateLimited { attempts: 1, retry_after: Some(delay), .. }
+                    if *delay == Duration::from_secs(3600)
+            ),
+            "{error:?}"
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn gateway_errors_are_retried() {
+        let server = server();
+        for status in [503, 502, 504] {
+            server.push_fault(Fault::Status(status));
+        }
+        let client = server
+            .client_builder("token")
+            .retry_policy(client(&server).retry_policy)
+            .max_attempts(3)
+            .build()
+            .unwrap();
+
+        let error = client.team(TeamId(1)).await.unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                error::Error::ServerError { status, .. } if *status == StatusCode::GATEWAY_TIMEOUT
+            ),
+            "{error:?}"
+        );
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn internal_server_errors_are_not_retried() {
+        let server = server();
+        server.push_fault(Fault::Status(500));
+
+        let error = client(&server).team(TeamId(1)).await.unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                error::Error::ServerError { status, .. }
+                    if *status == StatusCode::INTERNAL_SERVER_ERROR
+            ),
+            "{error:?}"
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn missing_resources_are_not_found() {
+        let server = server();
+
+        let error = client(&server).team(TeamId(2)).await.unwrap_err();
+
+        assert!(
+            matches!(&error, error::Error::NotFound { endpoint, .. } if endpoint == "/teams/2"),
+            "{error:?}"
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn wrong_tokens_are_unauthorized() {
+        let server = server();
+        server.require_token("secret");
+
+        let error = client(&server).team(TeamId(1)).await.unwrap_err();
+
+        assert!(
+            matches!(
+                &error,
+                error::Error::Unauthorized { status, .. } if *status == StatusCode::UNAUTHORIZED
+            ),
+            "{error:?}"
+        );
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn disconnects_are_retried_as_network_errors() {
+        let server = server();
+        server.push_fault(Fault::Disconnect);
+
+        let team = client(&server).team(TeamId(1)).await.unwrap();
+        assert_eq!(team.id, TeamId(1));
+        assert_eq!(server.requests().len(), 2);
+
+        for _ in 0..5 {
+            server.push_fault(Fault::Disconnect);
+        }
+        let error = client(&server).team(TeamId(1)).await.unwrap_err();
+        assert!(matches!(error, error::Error::ReqwestError(_)), "{error:?}");
+        assert_eq!(server.requests().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn all_programs_fetches_every_page() {
+        let programs: Vec<_> = (1..=30)
+            .map(|id| IdInfo {
+                id: ProgramId(id),
+                name: format!("Program {id}"),
+                code: None,
+            })
+            .collect();
+        let server = MockServer::start(MockData {
+            programs: programs.clone(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(client(&server).all_programs().await.unwrap(), programs);
+        assert_eq!(
+            server.requests(),
+            ["/api/v2/programs", "/api/v2/programs?page=2"]
+        );
+    }
+
+    /// Writes a cassette serving a page of teams 1-3, where team 2's grade is malformed.
+    fn malformed_page_cassette(name: &str) -> PathBuf {
+        use cassette::{Cassette, Interaction};
+
+        let mut data: Vec<_> = (1..=3)
+            .map(|id| serde_json::to_value(team(id)).unwrap())
+            .collect();
+        data[1]["grade"] = serde_json::json!(42);
+        let body = serde_json::json!({ "meta": PageMeta::default(), "data": data });
+
+        let path =
+            std::env::temp_dir().join(format!("robotevents-{name}-{}.json", std::process::id()));
+        Cassette {
+            interactions: vec![Interaction {
+                path: "/teams".to_owned(),
+                response: RecordedResponse {
+                    status: 200,
+                    headers: Vec::new(),
+                    body: body.to_string(),
+                },
+            }],
+        }
+        .save(&path)
+        .unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn deserialize_errors_report_the_path() {
+        let path = malformed_page_cassette("malformed-page");
+        let client = RobotEvents::builder("token")
+            .replay_from(&path)
+            .build()
+            .unwrap();
+
+        let error = client.teams(TeamsQuery::new()).await.unwrap_err();
+
+        let error::Error::DeserializeError {
+            endpoint,
+            path: field,
+            ..
+        } = error
+        else {
+            panic!("expected a deserialize error, got {error:?}");
+        };
+        assert_eq!(endpoint, "/teams");
+        assert_eq!(field, "data[1].grade");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn invalid_records_can_be_skipped() {
+        let path = malformed_page_cassette("skipped-records");
+        let client = RobotEvents::builder("token")
+            .replay_from(&path)
+            .skip_invalid_records(true)
+            .build()
+            .unwrap();
+
+        let page = client.teams(TeamsQuery::new()).await.unwrap();
+
+        assert_eq!(page.data, [team(1), team(3)]);
+        assert_eq!(page.skipped.len(), 1);
+        let skipped = &page.skipped[0];
+        assert_eq!(skipped.index, 1);
+        assert_eq!(skipped.path, "data[1].grade");
+        assert_eq!(skipped.raw["id"], 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn teams_by_number() {
+        let mut v5rc = team(1);
+        v5rc.number = "1082R".to_owned();
+        let mut viqrc = team(2);
+        viqrc.number = "2000A".to_owned();
+        viqrc.program = IdInfo {
+            id: ProgramId::VIQRC,
+            name: "VEX IQ Robotics Competition".to_owned(),
+            code: Some("VIQRC".to_owned()),
+        };
+        let server = MockServer::start(MockData {
+            teams: vec![v5rc.clone(), viqrc],
+            ..Default::default()
+        })
+        .unwrap();
+        let client = client(&server);
+
+        // Numbers are normalized before they're looked up.
+        assert_eq!(
+            client
+                .team_by_number(" 1082r", ProgramId::V5RC)
+                .await
+                .unwrap(),
+            Some(v5rc)
+        );
+        assert_eq!(
+            server.requests(),
+            ["/api/v2/teams?number%5B%5D=1082R&program%5B%5D=1"]
+        );
+
+        // 2000A is a VIQRC team.
+        assert_eq!(
+            client
+                .team_by_number("2000A", ProgramId::V5RC)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(server.requests().len(), 2);
+
+        // Invalid numbers are never requested.
+        for number in ["", "not a team", "BLRS"] {
+            assert_eq!(
+                client
+                    .team_by_number(number, ProgramId::V5RC)
+                    .await
+                    .unwrap(),
+                None
+            );
+        }
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn events_by_sku() {
+        let server = MockServer::start(MockData {
+            events: vec![event(1)],
+            ..Default::default()
+        })
+        .unwrap();
+        let client = client(&server);
+
+        assert_eq!(
+            client.event_by_sku(" re-v5rc-24-1 ").await.unwrap(),
+            Some(event(1))
+        );
+        assert_eq!(server.requests(), ["/api/v2/events?sku%5B%5D=RE-V5RC-24-1"]);
+
+        assert_eq!(client.event_by_sku("RE-V5RC-24-2").await.unwrap(), None);
+        assert_eq!(server.requests().len(), 2);
+
+        // Invalid SKUs are never requested.
+        for sku in ["", "RE-V5RC-1", "V5RC-24-1"] {
+            assert_eq!(client.event_by_sku(sku).await.unwrap(), None);
+        }
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn world_skills_go_through_the_v1_api() {
+        use crate::schema::v1::tests::standing_json;
+
+        let standings = [
+            standing_json(1, 1, "High School"),
+            standing_json(1, 2, "Middle School"),
+        ]
+        .map(|json| serde_json::from_value(json).unwrap());
+        let server = MockServer::start(MockData {
+            world_skills: standings.map(|standing| (SeasonId(190), standing)).to_vec(),
+            ..Default::default()
+        })
+        .unwrap();
+        server.push_fault(Fault::Status(503));
+
+        let standings = client(&server)
+            .world_skills(SeasonId(190), Grade::MiddleSchool, false)
+            .await
+            .unwrap();
+
+        let teams: Vec<_> = standings.iter().map(|standing| standing.team.id).collect();
+        assert_eq!(teams, [TeamId(2)]);
+        // The failed request was retried like a v2 request.
+        assert_eq!(
+            server.requests(),
+            ["/api/seasons/190/skills?grade_level=Middle%20School&post_season=0"; 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn zero_max_attempts_makes_one_attempt() {
+        let server = server();
+        server.push_fault(Fault::Status(503));
+        let client = server
+            .client_builder("token")
+            .max_attempts(0)
+            .build()
+            .unwrap();
+
+        let error = client.team(TeamId(1)).await.unwrap_err();
+
+        assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn page_urls_become_endpoints() {
+        let endpoint = |base: &str, url: &str| {
+            RobotEvents::builder("token")
+                .base_url(base)
+                .build()
+                .unwrap()
+                .endpoint_from_url(url)
+                .ok()
+        };
+        let page_2 = Some("/teams?page=2".to_owned());
+
+        assert_eq!(
+            endpoint(V2_API_BASE, &format!("{V2_API_BASE}/teams?page=2")),
+            page_2
+        );
+
+        // The API names its own host, even when the client is pointed somewhere else.
+        let api_url = "https://www.robotevents.com/api/v2/teams?page=2";
+        assert_eq!(endpoint("http://127.0.0.1:8080/api/v2", api_url), page_2);
+        assert_eq!(
+            endpoint("http://proxy.local/robotevents/api/v2/", api_url),
+            page_2
+        );
+        assert_eq!(
+            endpoint(
+                "http://proxy.local/robotevents/api/v2",
+                "http://proxy.local/robotevents/api/v2/teams?page=2"
+            ),
+            page_2
+        );
+
+        // URLs outside the API are rejected rather than appended to the base URL.
+        assert_eq!(
+            endpoint(V2_API_BASE, "https://example.com/teams?page=2"),
+            None
+        );
+        assert_eq!(
+            endpoint(V2_API_BASE, "https://www.robotevents.com/api/v20/teams"),
+            None
+        );
+        assert_eq!(endpoint(V2_API_BASE, "not a url"), None);
+    }
+
+    #[tokio::test]
+    async fn foreign_page_urls_are_errors() {
+        let client = RobotEvents::builder("token").build().unwrap();
+        let page = PaginatedResponse::<Team> {
+            meta: PageMeta {
+                next_page_url: Some("https://example.com/teams?page=2".to_owned()),
+                ..Default::default()
+            },
+            data: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        let error = page.next_page(&client).await.unwrap().unwrap_err();
+
+        assert!(matches!(
+            error,
+            error::Error::InvalidPageUrl { url } if url == "https://example.com/teams?page=2"
+        ));
+    }
+}
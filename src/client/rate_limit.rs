@@ -0,0 +1,251 @@
+//! Limiting how quickly requests are sent.
+//!
+//! The RobotEvents API responds with `429 Too Many Requests` when a client sends requests too
+//! quickly. A [`RateLimiter`] spaces requests out ahead of time so that limit is never reached.
+//! Clones of a [`RobotEvents`](super::RobotEvents) client share the limiter they were built
+//! with, so the limit applies across every task using the client.
+
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// A token bucket limiting how many requests are sent per minute.
+///
+/// The bucket holds up to [`burst`](Self::burst) tokens and refills at the configured rate.
+/// Each request takes a token, waiting for one to be refilled if the bucket is empty. Waiting
+/// requests are served in the order they arrived.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::{client::rate_limit::RateLimiter, RobotEvents};
+///
+/// let client = RobotEvents::builder("token")
+///     .rate_limiter(RateLimiter::new(120).burst(10))
+///     .build()?;
+/// # Ok::<(), robotevents::client::error::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_minute: u32,
+    burst: u32,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Tokens left in the bucket. Negative when requests are waiting for tokens that haven't
+    /// been refilled yet.
+    tokens: f64,
+    last_refill: Instant,
+    stats: RateLimiterStats,
+}
+
+/// Statistics on how requests have been delayed by a [`RateLimiter`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimiterStats {
+    /// The number of requests that have passed through the limiter.
+    pub requests: u64,
+    /// The number of requests that had to wait for a token.
+    pub queued_requests: u64,
+    /// The total time requests spent waiting for tokens.
+    pub total_queued: Duration,
+}
+
+impl RateLimiterStats {
+    /// The statistics for requests made since an `earlier` snapshot was taken.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn run(client: robotevents::RobotEvents) -> Result<(), robotevents::client::error::Error> {
+    /// use robotevents::query::TeamsQuery;
+    ///
+    /// let before = client.rate_limiter_stats().unwrap_or_default();
+    /// let teams = client.all_teams(TeamsQuery::new()).await?;
+    /// let queued = client.rate_limiter_stats().unwrap_or_default().since(&before);
+    ///
+    /// println!("fetched {} teams, queued for {:?}", teams.len(), queued.total_queued);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            requests: self.requests.saturating_sub(earlier.requests),
+            queued_requests: self.queued_requests.saturating_sub(earlier.queued_requests),
+            total_queued: self.total_queued.saturating_sub(earlier.total_queued),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `requests_per_minute` requests per minute, evenly spaced.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute: requests_per_minute.max(1),
+            burst: 1,
+            state: Mutex::new(BucketState {
+                tokens: 1.0,
+                last_refill: Instant::now(),
+                stats: RateLimiterStats::default(),
+            }),
+        }
+    }
+
+    /// Sets how many requests can be sent at once without waiting after the limiter has been
+    /// idle. Defaults to 1, which spaces every request out evenly.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self.state().tokens = f64::from(self.burst);
+        self
+    }
+
+    /// The number of requests allowed per minute.
+    pub fn requests_per_minute(&self) -> u32 {
+        self.requests_per_minute
+    }
+
+    /// A snapshot of how requests have been delayed so far.
+    pub fn stats(&self) -> RateLimiterStats {
+        self.state().stats
+    }
+
+    /// Waits until a request may be sent, returning how long it waited.
+    pub async fn acquire(&self) -> Duration {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            futures_timer::Delay::new(wait).await;
+        }
+        wait
+    }
+
+    /// Takes a token from the bucket, returning how long the caller must wait before the token
+    /// is available.
+    fn reserve(&self, now: Instant) -> Duration {
+        let tokens_per_second = f64::from(self.requests_per_minute) / 60.0;
+        let mut state = self.state();
+
+        let elapsed = now.saturating_duration_since(state.last_refill);
+        state.tokens =
+            (state.tokens + elapsed.as_secs_f64() * tokens_per_second).min(f64::from(self.burst));
+        state.last_refill = now;
+        state.tokens -= 1.0;
+
+        let wait = if state.tokens < 0.0 {
+            Duration::from_secs_f64(-state.tokens / tokens_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        state.stats.requests += 1;
+        if !wait.is_zero() {
+            state.stats.queued_requests += 1;
+            state.stats.total_queued += wait;
+        }
+        wait
+    }
+
+    fn state(&self) -> MutexGuard<'_, BucketState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn burst_drains_then_waits() {
+        let limiter = RateLimiter::new(60).burst(3);
+        let now = Instant::now();
+
+        let waits: Vec<_> = (0..5).map(|_| limiter.reserve(now)).collect();
+
+        assert_eq!(
+            waits,
+            [
+                Duration::ZERO,
+                Duration::ZERO,
+                Duration::ZERO,
+                SECOND,
+                2 * SECOND
+            ]
+        );
+    }
+
+    #[test]
+    fn tokens_refill_at_the_configured_rate() {
+        let limiter = RateLimiter::new(120);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+        assert_eq!(limiter.reserve(now), SECOND / 2);
+        // The second request's token is refilled after half a second, the next one's after
+        // another half second.
+        assert_eq!(limiter.reserve(now + SECOND / 2), SECOND / 2);
+        assert_eq!(limiter.reserve(now + 2 * SECOND), Duration::ZERO);
+    }
+
+    #[test]
+    fn refills_stop_at_the_burst_size() {
+        let limiter = RateLimiter::new(60).burst(2);
+        let now = Instant::now();
+        limiter.reserve(now);
+        limiter.reserve(now);
+
+        let later = now + 60 * SECOND;
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), SECOND);
+    }
+
+    #[test]
+    fn stats_count_queued_requests() {
+        let limiter = RateLimiter::new(60);
+        let now = Instant::now();
+        for _ in 0..3 {
+            limiter.reserve(now);
+        }
+        let before = limiter.stats();
+        limiter.reserve(now + 10 * SECOND);
+
+        assert_eq!(
+            before,
+            RateLimiterStats {
+                requests: 3,
+                queued_requests: 2,
+                total_queued: 3 * SECOND,
+            }
+        );
+        assert_eq!(
+            limiter.stats().since(&before),
+            RateLimiterStats {
+                requests: 1,
+                queued_requests: 0,
+                total_queued: Duration::ZERO,
+            }
+        );
+    }
+
+    #[test]
+    fn burst_resets_the_bucket() {
+        let limiter = RateLimiter::new(0).burst(0);
+        assert_eq!(limiter.requests_per_minute(), 1);
+        assert_eq!(limiter.state().tokens, 1.0);
+
+        let limiter = RateLimiter::new(60).burst(4);
+        assert_eq!(limiter.state().tokens, 4.0);
+        let now = Instant::now();
+        let free = (0..5)
+            .take_while(|_| limiter.reserve(now).is_zero())
+            .count();
+        assert_eq!(free, 4);
+    }
+}
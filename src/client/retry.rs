@@ -0,0 +1,184 @@
+//! Retrying failed requests.
+//!
+//! Requests that fail with `429 Too Many Requests`, a `502`, `503` or `504` status, a
+//! connection error or a timeout are retried according to a [`RetryPolicy`]. Between attempts,
+//! the client waits for the time given in the response's `Retry-After` header or, without one,
+//! an exponentially increasing delay. If the API asks to wait longer than the policy's
+//! [`max_delay`](RetryPolicy::max_delay), the request fails straight away instead.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime},
+};
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+
+/// How a client retries requests that fail for reasons that may not last.
+///
+/// The delay before retrying attempt `n` is `base_delay * 2^(n - 1)`, capped at `max_delay`.
+/// A random amount of up to `jitter` times that delay is then taken off, so that clients
+/// which failed at the same time don't all retry at the same time.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::{client::retry::RetryPolicy, RobotEvents};
+/// use std::time::Duration;
+///
+/// let client = RobotEvents::builder("token")
+///     .retry_policy(RetryPolicy {
+///         max_attempts: 8,
+///         base_delay: Duration::from_secs(1),
+///         ..Default::default()
+///     })
+///     .build()?;
+/// # Ok::<(), robotevents::client::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// How many times a request is attempted before giving up. A value of `0` is treated as
+    /// `1`.
+    pub max_attempts: u8,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The longest delay between attempts. Requests fail without being retried if the API
+    /// asks to wait longer than this with `Retry-After`.
+    pub max_delay: Duration,
+    /// The fraction of each delay, from `0.0` to `1.0`, that may be randomly taken off.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    /// Five attempts, starting with a half-second delay and backing off to at most 30 seconds,
+    /// with up to half of each delay removed at random.
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// The delay before retrying after the given failed attempt, counting from 1.
+    pub fn backoff(&self, attempt: u8) -> Duration {
+        let exponent = i32::from(attempt.saturating_sub(1));
+        let delay =
+            (self.base_delay.as_secs_f64() * 2f64.powi(exponent)).min(self.max_delay.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0) * random_fraction();
+
+        Duration::try_from_secs_f64(delay * (1.0 - jitter)).unwrap_or(self.max_delay)
+    }
+
+    /// Whether a response with this status is worth retrying.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Whether a request that failed with this error is worth retrying.
+    pub fn is_retryable_error(error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout() || error.is_request()
+    }
+}
+
+/// Reads how long the API asked to wait from a `Retry-After` header, given either as a number
+/// of seconds or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => Some(
+            httpdate::parse_http_date(value)
+                .ok()?
+                .duration_since(SystemTime::now())
+                // A date in the past means the request can be retried straight away.
+                .unwrap_or(Duration::ZERO),
+        ),
+    }
+}
+
+/// A random number in `0.0..1.0`, taken from the standard library's randomly seeded hasher
+/// to avoid depending on a random number generator crate.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(retry_after: &str) -> HeaderMap {
+        HeaderMap::from_iter([(RETRY_AFTER, retry_after.parse().unwrap())])
+    }
+
+    #[test]
+    fn retry_after_reads_seconds_and_dates() {
+        assert_eq!(retry_after(&headers("5")), Some(Duration::from_secs(5)));
+        assert_eq!(
+            retry_after(&headers("86400000")),
+            Some(Duration::from_secs(86_400_000))
+        );
+        assert_eq!(
+            retry_after(&headers("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+
+        let in_a_minute = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(61));
+        let delay = retry_after(&headers(&in_a_minute)).unwrap();
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(61));
+
+        assert_eq!(retry_after(&headers("soon")), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max_delay() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+            jitter: 0.0,
+            ..Default::default()
+        };
+        let delays: Vec<_> = (1..=5)
+            .map(|attempt| policy.backoff(attempt).as_secs())
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 5, 5]);
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for _ in 0..100 {
+            let delay = jittered.backoff(3);
+            assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+        }
+    }
+}
@@ -3,8 +3,13 @@ pub mod client;
 #[cfg(feature = "client")]
 pub mod query;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "mock")]
+pub mod mock;
+
+pub mod analysis;
 pub mod schema;
 
 #[cfg(feature = "client")]
 pub use client::*;
-
@@ -0,0 +1,899 @@
+//! An in-process stand-in for the RobotEvents API, for use in tests.
+//!
+//! [`MockServer`] serves the v2 endpoints and the v1 world skills standings from an
+//! in-memory [`MockData`] set over plain HTTP on a local port. It honours pagination
+//! (`page`, `per_page` and the `meta` links) and the most common filters of each endpoint,
+//! and can be told to fail upcoming requests with a [`Fault`] to exercise the client's
+//! retry logic.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use robotevents::{
+//!     mock::{Fault, MockData, MockServer},
+//!     query::TeamsQuery,
+//! };
+//!
+//! # async fn example(teams: Vec<robotevents::schema::Team>) -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::start(MockData {
+//!     teams,
+//!     ..Default::default()
+//! })?;
+//! let client = server.client_builder("token").build()?;
+//!
+//! // The first request is rate limited, the retry succeeds.
+//! server.push_fault(Fault::TooManyRequests {
+//!     retry_after: Some("0".to_owned()),
+//! });
+//! let teams = client.all_teams(TeamsQuery::new()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError,
+    },
+    thread::JoinHandle,
+};
+
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    schema::{
+        v1, Award, Event, EventId, IdInfo, Match, ProgramId, Ranking, Season, SeasonId, Skill,
+        Team, TeamId,
+    },
+    RobotEventsBuilder,
+};
+
+/// The number of items on a page when `per_page` isn't specified.
+const DEFAULT_PER_PAGE: usize = 25;
+
+/// The largest `per_page` the API accepts.
+const MAX_PER_PAGE: usize = 250;
+
+/// The data served by a [`MockServer`].
+#[derive(Default, Debug, Clone)]
+pub struct MockData {
+    pub programs: Vec<IdInfo<ProgramId>>,
+    pub seasons: Vec<Season>,
+    pub teams: Vec<Team>,
+    pub events: Vec<Event>,
+    /// Which teams are registered for which events, used by `/events/:id/teams`,
+    /// `/teams/:id/events` and the `event[]`/`team[]` filters.
+    pub registrations: Vec<(EventId, TeamId)>,
+    pub matches: Vec<Match>,
+    pub rankings: Vec<Ranking>,
+    pub finalist_rankings: Vec<Ranking>,
+    pub skills: Vec<Skill>,
+    pub awards: Vec<Award>,
+    /// The v1 world skills standings of each season, used by `/api/seasons/:id/skills`.
+    pub world_skills: Vec<(SeasonId, v1::SkillsStanding)>,
+}
+
+/// A failure that a [`MockServer`] responds with instead of serving a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Responds with `429 Too Many Requests` and the given `Retry-After` header, if any.
+    TooManyRequests { retry_after: Option<String> },
+
+    /// Responds with the given status code and an error body.
+    Status(u16),
+
+    /// Closes the connection without sending a response.
+    Disconnect,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    data: MockData,
+    faults: VecDeque<Fault>,
+    bearer_token: Option<String>,
+    requests: Vec<String>,
+}
+
+/// An HTTP server emulating the RobotEvents API.
+///
+/// The server runs on a background thread and is shut down when dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts a server on a free local port, serving `data`.
+    pub fn start(data: MockData) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            data,
+            ..Default::default()
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread = std::thread::spawn({
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A client hanging up early is not the server's problem.
+                        let _ = handle_connection(stream, addr, &state);
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// The base URL of the emulated v2 API, e.g. `http://127.0.0.1:4321/api/v2`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}/api/v2", self.addr)
+    }
+
+    /// The base URL of the emulated v1 API, e.g. `http://127.0.0.1:4321/api`.
+    pub fn v1_base_url(&self) -> String {
+        format!("http://{}/api", self.addr)
+    }
+
+    /// Creates a [`RobotEventsBuilder`] that points at this server.
+    pub fn client_builder(&self, bearer_token: impl AsRef<str>) -> RobotEventsBuilder {
+        RobotEventsBuilder::new(bearer_token)
+            .base_url(self.base_url())
+            .v1_base_url(self.v1_base_url())
+    }
+
+    /// Requires requests to be authenticated with `bearer_token`, responding with
+    /// `401 Unauthorized` otherwise. By default any token is accepted.
+    pub fn require_token(&self, bearer_token: impl Into<String>) {
+        self.state().bearer_token = Some(bearer_token.into());
+    }
+
+    /// Queues a fault. Each incoming request takes the oldest queued fault, if any,
+    /// and responds with it instead of being served.
+    pub fn push_fault(&self, fault: Fault) {
+        self.state().faults.push_back(fault);
+    }
+
+    /// Replaces the data being served.
+    pub fn set_data(&self, data: MockData) {
+        self.state().data = data;
+    }
+
+    /// The paths (including query strings) of every request received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.state().requests.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop up so it notices the shutdown flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// An HTTP response produced by the server.
+struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn json(value: &impl Serialize) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: serde_json::to_string(value).unwrap_or_default(),
+        }
+    }
+
+    fn error(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: json!({ "code": status, "message": reason_phrase(status) }).to_string(),
+        }
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    addr: SocketAddr,
+    state: &Mutex<State>,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let target = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or("/")
+        .to_owned();
+
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_owned());
+            }
+        }
+    }
+
+    let response = {
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.requests.push(target.clone());
+
+        match state.faults.pop_front() {
+            Some(Fault::Disconnect) => return Ok(()),
+            Some(Fault::TooManyRequests { retry_after }) => Response {
+                headers: retry_after
+                    .map(|retry_after| vec![("Retry-After", retry_after)])
+                    .unwrap_or_default(),
+                ..Response::error(429)
+            },
+            Some(Fault::Status(status)) => Response::error(status),
+            None => match &state.bearer_token {
+                Some(token) if authorization != Some(format!("Bearer {token}")) => {
+                    Response::error(401)
+                }
+                _ => route(&state.data, &format!("http://{addr}"), &target),
+            },
+        }
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len(),
+    )?;
+    for (name, value) in response.headers {
+        write!(stream, "{name}: {value}\r\n")?;
+    }
+    write!(stream, "\r\n{}", response.body)?;
+    stream.flush()
+}
+
+/// The query parameters of a request, percent-decoded.
+struct Params(Vec<(String, String)>);
+
+impl Params {
+    fn parse(query: &str) -> Self {
+        let decode = |s: &str| {
+            percent_decode_str(&s.replace('+', " "))
+                .decode_utf8_lossy()
+                .into_owned()
+        };
+
+        Self(
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| {
+                    let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                    (decode(key), decode(value))
+                })
+                .collect(),
+        )
+    }
+
+    fn get_all(&self, key: &str) -> Vec<&str> {
+        self.0
+            .iter()
+            .filter(|(k, _)| k == key || k.strip_suffix("[]") == Some(key))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.get_all(key).into_iter().next()
+    }
+}
+
+/// How a query parameter filters the records of an endpoint.
+enum Filter {
+    /// Keeps records where the value at a dot-separated path equals one of the parameter's
+    /// values. A `*` path segment matches any element of an array.
+    Field(&'static str),
+
+    /// Keeps records (events or teams) with a registration for one of the given IDs.
+    Registration,
+}
+
+const TEAM_FILTERS: &[(&str, Filter)] = &[
+    ("id", Filter::Field("id")),
+    ("number", Filter::Field("number")),
+    ("program", Filter::Field("program.id")),
+    ("grade", Filter::Field("grade")),
+    ("country", Filter::Field("location.country")),
+    ("registered", Filter::Field("registered")),
+    ("event", Filter::Registration),
+];
+
+const EVENT_FILTERS: &[(&str, Filter)] = &[
+    ("id", Filter::Field("id")),
+    ("sku", Filter::Field("sku")),
+    ("season", Filter::Field("season.id")),
+    ("level", Filter::Field("level")),
+    ("event_type", Filter::Field("event_type")),
+    ("region", Filter::Field("location.region")),
+    ("team", Filter::Registration),
+];
+
+const SEASON_FILTERS: &[(&str, Filter)] = &[
+    ("id", Filter::Field("id")),
+    ("program", Filter::Field("program.id")),
+];
+
+const MATCH_FILTERS: &[(&str, Filter)] = &[
+    ("event", Filter::Field("event.id")),
+    ("round", Filter::Field("round")),
+    ("instance", Filter::Field("instance")),
+    ("matchnum", Filter::Field("matchnum")),
+    ("team", Filter::Field("alliances.*.teams.*.team.id")),
+];
+
+const RANKING_FILTERS: &[(&str, Filter)] = &[
+    ("event", Filter::Field("event.id")),
+    ("team", Filter::Field("team.id")),
+    ("rank", Filter::Field("rank")),
+];
+
+const SKILL_FILTERS: &[(&str, Filter)] = &[
+    ("event", Filter::Field("event.id")),
+    ("team", Filter::Field("team.id")),
+    ("season", Filter::Field("season.id")),
+    ("type", Filter::Field("type")),
+];
+
+const AWARD_FILTERS: &[(&str, Filter)] = &[
+    ("event", Filter::Field("event.id")),
+    ("team", Filter::Field("teamWinners.*.team.id")),
+    ("winner", Filter::Field("individualWinners.*")),
+];
+
+fn route(data: &MockData, origin: &str, target: &str) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params = Params::parse(query);
+    let Some(path) = path.strip_prefix("/api/v2") else {
+        return route_v1(data, path, &params);
+    };
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let list = |records: Vec<Value>, filters: &[(&str, Filter)]| {
+        paginate(
+            data,
+            records,
+            filters,
+            &params,
+            &format!("{origin}/api/v2{path}"),
+            query,
+        )
+    };
+
+    match segments.as_slice() {
+        ["programs"] => list(to_values(&data.programs), &[]),
+        ["programs", id] => find(&data.programs, |p| p.id.to_string() == *id),
+
+        ["seasons"] => list(to_values(&data.seasons), SEASON_FILTERS),
+        ["seasons", id] => find(&data.seasons, |s| s.id.to_string() == *id),
+        ["seasons", id, "events"] => list(
+            to_values(
+                data.events
+                    .iter()
+                    .filter(|e| e.season.id.to_string() == *id),
+            ),
+            EVENT_FILTERS,
+        ),
+
+        ["teams"] => list(to_values(&data.teams), TEAM_FILTERS),
+        ["teams", id] => find(&data.teams, |t| t.id.to_string() == *id),
+        ["teams", id, "events"] => list(
+            to_values(data.events.iter().filter(|e| {
+                data.registrations
+                    .iter()
+                    .any(|(event, team)| *event == e.id && team.to_string() == *id)
+            })),
+            EVENT_FILTERS,
+        ),
+        ["teams", id, "matches"] => list(
+            to_values(data.matches.iter().filter(|m| {
+                m.alliances
+                    .iter()
+                    .any(|a| a.teams.iter().any(|t| t.team.id.to_string() == *id))
+            })),
+            MATCH_FILTERS,
+        ),
+        ["teams", id, "rankings"] => list(
+            to_values(
+                data.rankings
+                    .iter()
+                    .filter(|r| r.team.id.to_string() == *id),
+            ),
+            RANKING_FILTERS,
+        ),
+        ["teams", id, "skills"] => list(
+            to_values(data.skills.iter().filter(|s| s.team.id.to_string() == *id)),
+            SKILL_FILTERS,
+        ),
+        ["teams", id, "awards"] => list(
+            filter_field(to_values(&data.awards), "teamWinners.*.team.id", &[*id]),
+            AWARD_FILTERS,
+        ),
+
+        ["events"] => list(to_values(&data.events), EVENT_FILTERS),
+        ["events", id] => find(&data.events, |e| e.id.to_string() == *id),
+        ["events", id, "teams"] => list(
+            to_values(data.teams.iter().filter(|t| {
+                data.registrations
+                    .iter()
+                    .any(|(event, team)| *team == t.id && event.to_string() == *id)
+            })),
+            TEAM_FILTERS,
+        ),
+        ["events", id, "skills"] => list(
+            to_values(data.skills.iter().filter(|s| s.event.id.to_string() == *id)),
+            SKILL_FILTERS,
+        ),
+        ["events", id, "awards"] => list(
+            to_values(data.awards.iter().filter(|a| a.event.id.to_string() == *id)),
+            AWARD_FILTERS,
+        ),
+        ["events", id, "divisions", division, "matches"] => list(
+            to_values(data.matches.iter().filter(|m| {
+                m.event.id.to_string() == *id && m.division.id.to_string() == *division
+            })),
+            MATCH_FILTERS,
+        ),
+        ["events", id, "divisions", division, "rankings"] => list(
+            to_values(data.rankings.iter().filter(|r| {
+                r.event.id.to_string() == *id && r.division.id.to_string() == *division
+            })),
+            RANKING_FILTERS,
+        ),
+        ["events", id, "divisions", division, "finalistRankings"] => list(
+            to_values(data.finalist_rankings.iter().filter(|r| {
+                r.event.id.to_string() == *id && r.division.id.to_string() == *division
+            })),
+            RANKING_FILTERS,
+        ),
+
+        _ => Response::error(404),
+    }
+}
+
+fn route_v1(data: &MockData, path: &str, params: &Params) -> Response {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match segments.as_slice() {
+        ["api", "seasons", id, "skills"] => Response::json(
+            &data
+                .world_skills
+                .iter()
+                .filter(|(season, standing)| {
+                    season.to_string() == *id
+                        && params
+                            .get("grade_level")
+                            .is_none_or(|grade| standing.team.grade_level == grade)
+                })
+                .map(|(_, standing)| standing)
+                .collect::<Vec<_>>(),
+        ),
+        _ => Response::error(404),
+    }
+}
+
+fn to_values<'a, T: Serialize + 'a>(records: impl IntoIterator<Item = &'a T>) -> Vec<Value> {
+    records
+        .into_iter()
+        .filter_map(|record| serde_json::to_value(record).ok())
+        .collect()
+}
+
+fn find<T: Serialize>(records: &[T], predicate: impl Fn(&T) -> bool) -> Response {
+    match records.iter().find(|record| predicate(record)) {
+        Some(record) => Response::json(record),
+        None => Response::error(404),
+    }
+}
+
+/// Collects the scalar values found at a dot-separated path, expanding `*` segments.
+fn values_at<'a>(value: &'a Value, path: &[&str], out: &mut Vec<&'a Value>) {
+    match path.split_first() {
+        None => out.push(value),
+        Some((&"*", rest)) => {
+            if let Value::Array(items) = value {
+                for item in items {
+                    values_at(item, rest, out);
+                }
+            }
+        }
+        Some((key, rest)) => {
+            if let Some(child) = value.get(key) {
+                values_at(child, rest, out);
+            }
+        }
+    }
+}
+
+fn field_matches(record: &Value, path: &str, wanted: &[&str]) -> bool {
+    let path: Vec<&str> = path.split('.').collect();
+    let mut found = Vec::new();
+    values_at(record, &path, &mut found);
+
+    found.into_iter().any(|value| {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        wanted
+            .iter()
+            .any(|wanted| value.eq_ignore_ascii_case(wanted))
+    })
+}
+
+fn filter_field(records: Vec<Value>, path: &str, wanted: &[&str]) -> Vec<Value> {
+    records
+        .into_iter()
+        .filter(|record| field_matches(record, path, wanted))
+        .collect()
+}
+
+fn paginate(
+    data: &MockData,
+    mut records: Vec<Value>,
+    filters: &[(&str, Filter)],
+    params: &Params,
+    path: &str,
+    query: &str,
+) -> Response {
+    for (key, filter) in filters {
+        let wanted = params.get_all(key);
+        if wanted.is_empty() {
+            continue;
+        }
+
+        records = match filter {
+            Filter::Field(field) => filter_field(records, field, &wanted),
+            Filter::Registration => records
+                .into_iter()
+                .filter(|record| {
+                    let id = record.get("id").map(Value::to_string).unwrap_or_default();
+                    data.registrations.iter().any(|(event, team)| {
+                        // Events are filtered by team, and teams by event.
+                        let (this, other) = if *key == "team" {
+                            (event.to_string(), team.to_string())
+                        } else {
+                            (team.to_string(), event.to_string())
+                        };
+                        this == id && wanted.contains(&other.as_str())
+                    })
+                })
+                .collect(),
+        };
+    }
+
+    let per_page = params
+        .get("per_page")
+        .and_then(|per_page| per_page.parse().ok())
+        .unwrap_or(DEFAULT_PER_PAGE)
+        .clamp(1, MAX_PER_PAGE);
+    let total = records.len();
+    let last_page = total.div_ceil(per_page).max(1);
+    let current_page = params
+        .get("page")
+        .and_then(|page| page.parse().ok())
+        .unwrap_or(1)
+        .max(1);
+
+    let start = ((current_page - 1) * per_page).min(total);
+    let end = (start + per_page).min(total);
+    let page_data: Vec<Value> = records.drain(start..end).collect();
+
+    // Page links keep every parameter of the original request except `page`.
+    let base_query: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !pair.starts_with("page="))
+        .collect();
+    let page_url = |page: usize| {
+        let mut pairs = base_query.clone();
+        let page = format!("page={page}");
+        pairs.push(&page);
+        format!("{path}?{}", pairs.join("&"))
+    };
+
+    Response::json(&json!({
+        "meta": {
+            "current_page": current_page,
+            "first_page_url": page_url(1),
+            "from": if page_data.is_empty() { None } else { Some(start + 1) },
+            "to": if page_data.is_empty() { None } else { Some(end) },
+            "last_page": last_page,
+            "last_page_url": page_url(last_page),
+            "prev_page_url": (current_page > 1).then(|| page_url(current_page - 1)),
+            "next_page_url": (current_page < last_page).then(|| page_url(current_page + 1)),
+            "path": path,
+            "per_page": per_page,
+            "total": total,
+        },
+        "data": page_data,
+    }))
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::{
+        client::retry::RetryPolicy,
+        query::{EventsQuery, PaginatedQuery, TeamsQuery},
+        schema::{Coordinates, EventLevel, Grade, Location, SeasonId},
+        RobotEvents,
+    };
+
+    pub(crate) fn location() -> Location {
+        Location {
+            venue: None,
+            address_1: "1 Main St".to_owned(),
+            address_2: None,
+            city: "Greenville".to_owned(),
+            region: Some("Texas".to_owned()),
+            postcode: None,
+            country: "United States".to_owned(),
+            coordinates: Coordinates {
+                lat: None,
+                lon: None,
+            },
+        }
+    }
+
+    pub(crate) fn team(id: i32) -> Team {
+        Team {
+            id: TeamId(id),
+            number: format!("{id}A"),
+            team_name: format!("Team {id}"),
+            robot_name: None,
+            organization: None,
+            location: location(),
+            registered: true,
+            program: IdInfo {
+                id: ProgramId::V5RC,
+                name: "VEX V5 Robotics Competition".to_owned(),
+                code: Some("V5RC".to_owned()),
+            },
+            grade: Grade::HighSchool,
+        }
+    }
+
+    pub(crate) fn event(id: i32) -> Event {
+        Event {
+            id: EventId(id),
+            sku: format!("RE-V5RC-24-{id}"),
+            name: format!("Event {id}"),
+            start: "2024-10-05T00:00:00-05:00".to_owned(),
+            end: "2024-10-05T00:00:00-05:00".to_owned(),
+            season: IdInfo {
+                id: SeasonId(190),
+                name: "High Stakes".to_owned(),
+                code: None,
+            },
+            program: team(0).program,
+            location: location(),
+            locations: HashMap::new(),
+            divisions: Vec::new(),
+            level: EventLevel::Signature,
+            ongoing: false,
+            awards_finalized: false,
+            event_type: None,
+        }
+    }
+
+    /// A client for `server` that retries quickly.
+    pub(crate) fn client(server: &MockServer) -> RobotEvents {
+        server
+            .client_builder("token")
+            .retry_policy(RetryPolicy {
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+                ..Default::default()
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn teams(count: i32) -> MockData {
+        MockData {
+            teams: (1..=count).map(team).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn all_teams_collects_every_page() {
+        let data = teams(60);
+        let server = MockServer::start(data.clone()).unwrap();
+
+        let teams = client(&server).all_teams(TeamsQuery::new()).await.unwrap();
+
+        assert_eq!(teams, data.teams);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn pages_have_meta_links() {
+        let server = MockServer::start(teams(60)).unwrap();
+
+        let page = client(&server)
+            .teams(TeamsQuery::new().per_page(25).page(2))
+            .await
+            .unwrap();
+        let base = server.base_url();
+
+        assert_eq!(page.data.first().map(|team| team.id), Some(TeamId(26)));
+        assert_eq!((page.meta.from, page.meta.to), (Some(26), Some(50)));
+        assert_eq!((page.meta.current_page, page.meta.last_page), (2, 3));
+        assert_eq!(page.meta.total, 60);
+        assert_eq!(
+            page.meta.next_page_url,
+            Some(format!("{base}/teams?per_page=25&page=3"))
+        );
+        assert_eq!(
+            page.meta.prev_page_url,
+            Some(format!("{base}/teams?per_page=25&page=1"))
+        );
+    }
+
+    #[tokio::test]
+    async fn next_page_url_round_trips_through_paginate() {
+        let data = teams(23);
+        let server = MockServer::start(data.clone()).unwrap();
+
+        let teams: Vec<Team> = client(&server)
+            .teams_stream(TeamsQuery::new().per_page(10))
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(teams, data.teams);
+        assert_eq!(
+            server.requests(),
+            [
+                "/api/v2/teams?per_page=10",
+                "/api/v2/teams?per_page=10&page=2",
+                "/api/v2/teams?per_page=10&page=3",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn registration_filters() {
+        let server = MockServer::start(MockData {
+            teams: (1..=3).map(team).collect(),
+            events: (10..=11).map(event).collect(),
+            registrations: vec![
+                (EventId(10), TeamId(1)),
+                (EventId(10), TeamId(2)),
+                (EventId(11), TeamId(3)),
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+        let client = client(&server);
+
+        let teams = client
+            .all_teams(TeamsQuery::new().event(EventId(10)))
+            .await
+            .unwrap();
+        let ids: Vec<_> = teams.iter().map(|team| team.id).collect();
+        assert_eq!(ids, [TeamId(1), TeamId(2)]);
+
+        let events = client
+            .all_events(EventsQuery::new().team(TeamId(3)))
+            .await
+            .unwrap();
+        let ids: Vec<_> = events.iter().map(|event| event.id).collect();
+        assert_eq!(ids, [EventId(11)]);
+
+        let teams = client
+            .all_event_teams(EventId(11), Default::default())
+            .await
+            .unwrap();
+        let ids: Vec<_> = teams.iter().map(|team| team.id).collect();
+        assert_eq!(ids, [TeamId(3)]);
+    }
+
+    #[tokio::test]
+    async fn field_filters() {
+        let mut data = teams(3);
+        data.teams[1].grade = Grade::MiddleSchool;
+        let server = MockServer::start(data).unwrap();
+
+        let teams = client(&server)
+            .all_teams(TeamsQuery::new().grade(Grade::MiddleSchool))
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = teams.iter().map(|team| team.id).collect();
+        assert_eq!(ids, [TeamId(2)]);
+    }
+
+    #[tokio::test]
+    async fn requests_include_retries() {
+        let server = MockServer::start(teams(1)).unwrap();
+        server.push_fault(Fault::Status(503));
+        server.push_fault(Fault::TooManyRequests {
+            retry_after: Some("0".to_owned()),
+        });
+
+        let team = client(&server).team(TeamId(1)).await.unwrap();
+
+        assert_eq!(team.id, TeamId(1));
+        assert_eq!(
+            server.requests(),
+            ["/api/v2/teams/1", "/api/v2/teams/1", "/api/v2/teams/1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_paths_are_not_found() {
+        let server = MockServer::start(MockData::default()).unwrap();
+
+        let error = client(&server).request("/nowhere").await.unwrap_err();
+
+        assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+    }
+}
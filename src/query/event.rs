@@ -1,13 +1,31 @@
-use super::{impl_query_display, impl_paginated_query};
-use itertools::join;
-use std::collections::HashMap;
+use super::{impl_paginated_query, impl_query_display, IntoQueryDate, QueryParams};
 
-use crate::schema::{EventLevel, EventType, Grade, MatchRound, SkillType};
+use crate::schema::{
+    EventId, EventLevel, EventSku, EventType, Grade, MatchRound, SeasonId, SkillType, TeamId,
+    TeamNumber,
+};
 
 /// Queries for the RobotEvents `/events` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::EventsQuery;
+/// use robotevents::schema::{EventLevel, SeasonId};
+///
+/// let query = EventsQuery::new()
+///     .seasons(&[SeasonId(181), SeasonId(190)])
+///     .region("New York".to_owned())
+///     .level(EventLevel::Signature);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?level%5B%5D=Signature&region=New%20York&season%5B%5D=181&season%5B%5D=190"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct EventsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(EventsQuery);
@@ -18,85 +36,102 @@ impl EventsQuery {
         Self::default()
     }
 
-    pub fn id(mut self, id: i32) -> Self {
-        self.query.insert("id%5B%5D", id.to_string());
+    pub fn id(mut self, id: EventId) -> Self {
+        self.query.push("id[]", id);
         self
     }
-    pub fn ids(mut self, ids: &[i32]) -> Self {
-        self.query.insert("id%5B%5D", join(ids, ","));
+    pub fn ids(mut self, ids: &[EventId]) -> Self {
+        self.query.extend("id[]", ids);
         self
     }
 
-    pub fn sku(mut self, sku: i32) -> Self {
-        self.query.insert("sku%5B%5D", sku.to_string());
+    pub fn sku(mut self, sku: EventSku) -> Self {
+        self.query.push("sku[]", sku);
         self
     }
-    pub fn skus(mut self, skus: &[i32]) -> Self {
-        self.query.insert("sku%5B%5D", join(skus, ","));
+    pub fn skus(mut self, skus: &[EventSku]) -> Self {
+        self.query.extend("sku[]", skus);
         self
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
-    pub fn season(mut self, season: i32) -> Self {
-        self.query.insert("season%5B%5D", season.to_string());
+    pub fn season(mut self, season: SeasonId) -> Self {
+        self.query.push("season[]", season);
         self
     }
-    pub fn seasons(mut self, seasons: &[i32]) -> Self {
-        self.query.insert("season%5B%5D", join(seasons, ","));
+    pub fn seasons(mut self, seasons: &[SeasonId]) -> Self {
+        self.query.extend("season[]", seasons);
         self
     }
 
-    pub fn start(mut self, start: String) -> Self {
-        self.query.insert("start", start);
+    pub fn start(mut self, start: impl IntoQueryDate) -> Self {
+        self.query.set("start", start.into_query_date());
         self
     }
-    pub fn end(mut self, end: String) -> Self {
-        self.query.insert("end", end);
+    pub fn end(mut self, end: impl IntoQueryDate) -> Self {
+        self.query.set("end", end.into_query_date());
         self
     }
 
     pub fn region(mut self, region: String) -> Self {
-        self.query.insert("start", region);
+        self.query.set("region", region);
         self
     }
 
     pub fn level(mut self, level: EventLevel) -> Self {
-        self.query.insert("level%5B%5D", level.to_string());
+        self.query.push("level[]", level);
         self
     }
     pub fn levels(mut self, levels: &[EventLevel]) -> Self {
-        self.query.insert("season%5B%5D", join(levels, ","));
+        self.query.extend("level[]", levels);
         self
     }
 
     pub fn my_events(mut self, my_events: bool) -> Self {
-        self.query.insert("my_events", my_events.to_string());
+        self.query.set("my_events", my_events);
         self
     }
 
     pub fn event_type(mut self, event_type: EventType) -> Self {
-        self.query
-            .insert("event_type%5B%5D", event_type.to_string());
+        self.query.push("event_type[]", event_type);
         self
     }
     pub fn event_types(mut self, event_types: &[EventType]) -> Self {
-        self.query.insert("season%5B%5D", join(event_types, ","));
+        self.query.extend("event_type[]", event_types);
         self
     }
 }
 
 /// Queries for the RobotEvents `/event/:id/teams` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::EventTeamsQuery;
+/// use robotevents::schema::Grade;
+///
+/// let query = EventTeamsQuery::new()
+///     .numbers(&["1234A".parse()?, "99999B".parse()?])
+///     .grade(Grade::MiddleSchool)
+///     .registered(true);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?grade%5B%5D=Middle%20School&number%5B%5D=1234A&number%5B%5D=99999B&registered=true"
+/// );
+/// # Ok::<(), robotevents::schema::TeamNumberError>(())
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct EventTeamsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(EventTeamsQuery);
@@ -107,48 +142,64 @@ impl EventTeamsQuery {
         Self::default()
     }
 
-    pub fn number(mut self, number: String) -> Self {
-        self.query.insert("number%5B%5D", number.to_string());
+    pub fn number(mut self, number: TeamNumber) -> Self {
+        self.query.push("number[]", number);
         self
     }
-    pub fn numbers(mut self, numbers: Vec<String>) -> Self {
-        self.query.insert("number%5B%5D", join(numbers, ","));
+    pub fn numbers(mut self, numbers: &[TeamNumber]) -> Self {
+        self.query.extend("number[]", numbers);
         self
     }
 
-    pub fn registered(mut self, number: String) -> Self {
-        self.query.insert("registered", number.to_string());
+    pub fn registered(mut self, registered: bool) -> Self {
+        self.query.set("registered", registered);
         self
     }
 
     pub fn grade(mut self, grade: Grade) -> Self {
-        self.query.insert("grade%5B%5D", grade.to_string());
+        self.query.push("grade[]", grade);
         self
     }
     pub fn grades(mut self, grades: &[Grade]) -> Self {
-        self.query.insert("grade%5B%5D", join(grades, ","));
+        self.query.extend("grade[]", grades);
         self
     }
 
-    pub fn country(mut self, country: i32) -> Self {
-        self.query.insert("country%5B%5D", country.to_string());
+    pub fn country(mut self, country: String) -> Self {
+        self.query.push("country[]", country);
         self
     }
-    pub fn countries(mut self, countrys: &[i32]) -> Self {
-        self.query.insert("country%5B%5D", join(countrys, ","));
+    pub fn countries(mut self, countries: &[String]) -> Self {
+        self.query.extend("country[]", countries);
         self
     }
 
     pub fn my_teams(mut self, my_teams: bool) -> Self {
-        self.query.insert("myTeams", my_teams.to_string());
+        self.query.set("myTeams", my_teams);
         self
     }
 }
 
 /// Queries for the RobotEvents `/events/:id/skills` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::EventSkillsQuery;
+/// use robotevents::schema::{SkillType, TeamId};
+///
+/// let query = EventSkillsQuery::new()
+///     .team(TeamId(5))
+///     .skill_types(&[SkillType::Driver, SkillType::Programming]);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?team%5B%5D=5&type%5B%5D=driver&type%5B%5D=programming"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct EventSkillsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(EventSkillsQuery);
@@ -159,29 +210,42 @@ impl EventSkillsQuery {
         Self::default()
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
     pub fn skill_type(mut self, skill_type: SkillType) -> Self {
-        self.query.insert("type%5B%5D", skill_type.to_string());
+        self.query.push("type[]", skill_type);
         self
     }
     pub fn skill_types(mut self, skill_types: &[SkillType]) -> Self {
-        self.query.insert("type%5B%5D", join(skill_types, ","));
+        self.query.extend("type[]", skill_types);
         self
     }
 }
 
 /// Queries for the RobotEvents `/event/:id/awards` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::EventAwardsQuery;
+///
+/// let query = EventAwardsQuery::new().winner("Team 1234A & friends".to_owned());
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?winner%5B%5D=Team%201234A%20%26%20friends"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct EventAwardsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(EventAwardsQuery);
@@ -192,29 +256,46 @@ impl EventAwardsQuery {
         Self::default()
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
     pub fn winner(mut self, winner: String) -> Self {
-        self.query.insert("winner%5B%5D", winner.to_string());
+        self.query.push("winner[]", winner);
         self
     }
     pub fn winners(mut self, winners: Vec<String>) -> Self {
-        self.query.insert("winner%5B%5D", join(winners, ","));
+        self.query.extend("winner[]", winners);
         self
     }
 }
 
 /// Queries for the RobotEvents `/event/:id/divisions/:div/matches` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::DivisionMatchesQuery;
+/// use robotevents::schema::{MatchRound, TeamId};
+///
+/// let query = DivisionMatchesQuery::new()
+///     .round(MatchRound::Qualification)
+///     .teams(&[TeamId(1), TeamId(2)])
+///     .matchnum(12);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?matchnum%5B%5D=12&round%5B%5D=2&team%5B%5D=1&team%5B%5D=2"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct DivisionMatchesQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(DivisionMatchesQuery);
@@ -225,51 +306,61 @@ impl DivisionMatchesQuery {
         Self::default()
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
     pub fn round(mut self, round: MatchRound) -> Self {
-        self.query.insert("round%5B%5D", (round as i32).to_string());
+        self.query.push("round[]", round.id());
         self
     }
     pub fn rounds(mut self, rounds: &[MatchRound]) -> Self {
-        self.query.insert(
-            "round%5B%5D",
-            join(rounds.iter().map(|round| round.clone() as i32), ","),
-        );
+        self.query
+            .extend("round[]", rounds.iter().map(|round| round.id()));
         self
     }
 
     pub fn instance(mut self, instance: i32) -> Self {
-        self.query.insert("instance%5B%5D", instance.to_string());
+        self.query.push("instance[]", instance);
         self
     }
     pub fn instances(mut self, instances: &[i32]) -> Self {
-        self.query.insert("instance%5B%5D", join(instances, ","));
+        self.query.extend("instance[]", instances);
         self
     }
 
     pub fn matchnum(mut self, matchnum: i32) -> Self {
-        self.query.insert("matchnum%5B%5D", matchnum.to_string());
+        self.query.push("matchnum[]", matchnum);
         self
     }
     pub fn matchnums(mut self, matchnums: &[i32]) -> Self {
-        self.query.insert("matchnum%5B%5D", join(matchnums, ","));
+        self.query.extend("matchnum[]", matchnums);
         self
     }
 }
 
-
 /// Queries for the RobotEvents `/event/:id/divisions/:div/finalistRankings` and `/event/:id/divisions/:div/rankings` endpoints.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::{DivisionRankingsQuery, PaginatedQuery};
+///
+/// let query = DivisionRankingsQuery::new().ranks(&[1, 2, 3]).per_page(250);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?per_page=250&rank%5B%5D=1&rank%5B%5D=2&rank%5B%5D=3"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct DivisionRankingsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(DivisionRankingsQuery);
@@ -280,21 +371,21 @@ impl DivisionRankingsQuery {
         Self::default()
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
     pub fn rank(mut self, rank: i32) -> Self {
-        self.query.insert("rank%5B%5D", rank.to_string());
+        self.query.push("rank[]", rank);
         self
     }
     pub fn ranks(mut self, ranks: &[i32]) -> Self {
-        self.query.insert("rank%5B%5D", join(ranks, ","));
+        self.query.extend("rank[]", ranks);
         self
     }
 }
@@ -6,11 +6,129 @@ pub use event::*;
 pub use season::*;
 pub use team::*;
 
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::collections::BTreeMap;
+
 pub trait PaginatedQuery {
     fn page(self, page: i32) -> Self;
     fn per_page(self, per_page: i32) -> Self;
 }
 
+/// A date that can be passed to the date filters of a query, such as [`EventsQuery::start`].
+///
+/// Strings are sent to the API as-is. With the `chrono` feature enabled, [`chrono::DateTime`]s
+/// are converted to UTC and sent in RFC 3339 format (e.g. `2024-04-25T13:00:00Z`), and
+/// [`chrono::NaiveDate`]s are sent as `YYYY-MM-DD`.
+pub trait IntoQueryDate {
+    fn into_query_date(self) -> String;
+}
+
+impl IntoQueryDate for String {
+    fn into_query_date(self) -> String {
+        self
+    }
+}
+
+impl IntoQueryDate for &str {
+    fn into_query_date(self) -> String {
+        self.to_owned()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> IntoQueryDate for chrono::DateTime<Tz> {
+    fn into_query_date(self) -> String {
+        self.with_timezone(&chrono::Utc)
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl IntoQueryDate for chrono::NaiveDate {
+    fn into_query_date(self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Characters that are left as-is when percent-encoding query keys and values.
+///
+/// Matches the "unreserved" set from RFC 3986.
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The parameters of a query, stored in a deterministic (alphabetical) order.
+///
+/// Array parameters (keys ending in `[]`) may hold multiple values, which are written
+/// as repeated `key[]=value` pairs. Keys and values are percent-encoded when the
+/// parameters are displayed.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct QueryParams {
+    params: BTreeMap<&'static str, Vec<String>>,
+}
+
+impl QueryParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a single-valued parameter, replacing any previous value.
+    pub fn set(&mut self, key: &'static str, value: impl ToString) {
+        self.params.insert(key, vec![value.to_string()]);
+    }
+
+    /// Adds a value to an array parameter.
+    pub fn push(&mut self, key: &'static str, value: impl ToString) {
+        self.params.entry(key).or_default().push(value.to_string());
+    }
+
+    /// Adds several values to an array parameter.
+    pub fn extend<T: ToString>(&mut self, key: &'static str, values: impl IntoIterator<Item = T>) {
+        self.params
+            .entry(key)
+            .or_default()
+            .extend(values.into_iter().map(|value| value.to_string()));
+    }
+
+    /// Iterates over every key-value pair in the order they are written to the query string.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.params
+            .iter()
+            .flat_map(|(key, values)| values.iter().map(move |value| (*key, value.as_str())))
+    }
+}
+
+impl PaginatedQuery for QueryParams {
+    fn page(mut self, page: i32) -> Self {
+        self.set("page", page);
+        self
+    }
+
+    fn per_page(mut self, per_page: i32) -> Self {
+        self.set("per_page", per_page);
+        self
+    }
+}
+
+impl std::fmt::Display for QueryParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            write!(
+                f,
+                "{}{}={}",
+                if i == 0 { '?' } else { '&' },
+                utf8_percent_encode(key, QUERY_ENCODE_SET),
+                utf8_percent_encode(value, QUERY_ENCODE_SET),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Implements [`std::fmt::Display`] for a query struct.
 ///
 /// This will convert the query parameters provided to the struct into a single query string.
@@ -19,19 +137,7 @@ macro_rules! impl_query_display {
     ( $name:ident ) => {
         impl ::std::fmt::Display for $name {
             fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                let mut iter = self.query.iter();
-
-                if let Some(first) = iter.next() {
-                    write!(f, "?{}={}", first.0, first.1)?;
-                } else {
-                    return Ok(());
-                }
-
-                for (key, value) in iter {
-                    write!(f, "&{key}={value}")?;
-                }
-
-                Ok(())
+                ::std::fmt::Display::fmt(&self.query, f)
             }
         }
     };
@@ -41,14 +147,14 @@ macro_rules! impl_query_display {
 #[macro_export]
 macro_rules! impl_paginated_query {
     ( $name:ident ) => {
-        impl crate::query::PaginatedQuery for $name {
+        impl $crate::query::PaginatedQuery for $name {
             fn page(mut self, page: i32) -> Self {
-                self.query.insert("page", page.to_string());
+                self.query.set("page", page);
                 self
             }
 
             fn per_page(mut self, per_page: i32) -> Self {
-                self.query.insert("per_page", per_page.to_string());
+                self.query.set("per_page", per_page);
                 self
             }
         }
@@ -57,4 +163,3 @@ macro_rules! impl_paginated_query {
 
 pub use impl_paginated_query;
 pub use impl_query_display;
-
@@ -1,13 +1,21 @@
-use super::{impl_query_display, impl_paginated_query};
-use crate::schema::EventLevel;
-
-use itertools::join;
-use std::collections::HashMap;
+use super::{impl_paginated_query, impl_query_display, IntoQueryDate, QueryParams};
+use crate::schema::{EventLevel, EventSku, ProgramId, SeasonId, TeamId};
 
 /// Queries for the RobotEvents `/seasons` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::SeasonsQuery;
+/// use robotevents::schema::ProgramId;
+///
+/// let query = SeasonsQuery::new().program(ProgramId(1)).active(true);
+///
+/// assert_eq!(query.to_string(), "?active=true&program%5B%5D=1");
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct SeasonsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(SeasonsQuery);
@@ -18,52 +26,69 @@ impl SeasonsQuery {
         Self::default()
     }
 
-    pub fn id(mut self, id: i32) -> Self {
-        self.query.insert("id%5B%5D", id.to_string());
+    pub fn id(mut self, id: SeasonId) -> Self {
+        self.query.push("id[]", id);
         self
     }
-    pub fn ids(mut self, ids: &[i32]) -> Self {
-        self.query.insert("id%5B%5D", join(ids, ","));
+    pub fn ids(mut self, ids: &[SeasonId]) -> Self {
+        self.query.extend("id[]", ids);
         self
     }
 
-    pub fn program(mut self, program: i32) -> Self {
-        self.query.insert("program%5B%5D", program.to_string());
+    pub fn program(mut self, program: ProgramId) -> Self {
+        self.query.push("program[]", program);
         self
     }
-    pub fn programs(mut self, programs: &[i32]) -> Self {
-        self.query.insert("program%5B%5D", join(programs, ","));
+    pub fn programs(mut self, programs: &[ProgramId]) -> Self {
+        self.query.extend("program[]", programs);
         self
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
-    pub fn start(mut self, start: String) -> Self {
-        self.query.insert("start", start);
+    pub fn start(mut self, start: impl IntoQueryDate) -> Self {
+        self.query.set("start", start.into_query_date());
         self
     }
-    pub fn end(mut self, end: String) -> Self {
-        self.query.insert("start", end);
+    pub fn end(mut self, end: impl IntoQueryDate) -> Self {
+        self.query.set("end", end.into_query_date());
         self
     }
 
     pub fn active(mut self, active: bool) -> Self {
-        self.query.insert("active", active.to_string());
+        self.query.set("active", active);
         self
     }
 }
 
 /// Queries for the RobotEvents `/seasons/:id/events` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::SeasonEventsQuery;
+/// use robotevents::schema::{EventLevel, TeamId};
+///
+/// let query = SeasonEventsQuery::new()
+///     .team(TeamId(139))
+///     .levels(&[EventLevel::State, EventLevel::National])
+///     .start("2024-01-01");
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?level%5B%5D=State&level%5B%5D=National&start=2024-01-01&team%5B%5D=139"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct SeasonEventsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(SeasonEventsQuery);
@@ -74,39 +99,39 @@ impl SeasonEventsQuery {
         Self::default()
     }
 
-    pub fn sku(mut self, sku: i32) -> Self {
-        self.query.insert("sku%5B%5D", sku.to_string());
+    pub fn sku(mut self, sku: EventSku) -> Self {
+        self.query.push("sku[]", sku);
         self
     }
-    pub fn skus(mut self, skus: &[i32]) -> Self {
-        self.query.insert("sku%5B%5D", join(skus, ","));
+    pub fn skus(mut self, skus: &[EventSku]) -> Self {
+        self.query.extend("sku[]", skus);
         self
     }
 
-    pub fn team(mut self, team: i32) -> Self {
-        self.query.insert("team%5B%5D", team.to_string());
+    pub fn team(mut self, team: TeamId) -> Self {
+        self.query.push("team[]", team);
         self
     }
-    pub fn teams(mut self, teams: &[i32]) -> Self {
-        self.query.insert("team%5B%5D", join(teams, ","));
+    pub fn teams(mut self, teams: &[TeamId]) -> Self {
+        self.query.extend("team[]", teams);
         self
     }
 
-    pub fn start(mut self, start: String) -> Self {
-        self.query.insert("start", start);
+    pub fn start(mut self, start: impl IntoQueryDate) -> Self {
+        self.query.set("start", start.into_query_date());
         self
     }
-    pub fn end(mut self, end: String) -> Self {
-        self.query.insert("end", end);
+    pub fn end(mut self, end: impl IntoQueryDate) -> Self {
+        self.query.set("end", end.into_query_date());
         self
     }
 
     pub fn level(mut self, level: EventLevel) -> Self {
-        self.query.insert("level%5B%5D", level.to_string());
+        self.query.push("level[]", level);
         self
     }
     pub fn levels(mut self, levels: &[EventLevel]) -> Self {
-        self.query.insert("season%5B%5D", join(levels, ","));
+        self.query.extend("level[]", levels);
         self
     }
 }
@@ -1,14 +1,33 @@
-use super::{impl_paginated_query, impl_query_display};
+use super::{impl_paginated_query, impl_query_display, IntoQueryDate, QueryParams};
 
-use itertools::join;
-use std::collections::HashMap;
-
-use crate::schema::{EventLevel, Grade, MatchRound, SkillType};
+use crate::schema::{
+    EventId, EventLevel, EventSku, Grade, MatchRound, ProgramId, SeasonId, SkillType, TeamId,
+    TeamNumber,
+};
 
 /// Queries for the RobotEvents `/teams` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::{PaginatedQuery, TeamsQuery};
+/// use robotevents::schema::ProgramId;
+///
+/// let query = TeamsQuery::new()
+///     .number("1234a".parse()?)
+///     .program(ProgramId(1))
+///     .countries(&["US".to_owned(), "CA".to_owned()])
+///     .page(2);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?country%5B%5D=US&country%5B%5D=CA&number%5B%5D=1234A&page=2&program%5B%5D=1"
+/// );
+/// # Ok::<(), robotevents::schema::TeamNumberError>(())
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TeamsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(TeamsQuery);
@@ -19,69 +38,67 @@ impl TeamsQuery {
         Self::default()
     }
 
-    pub fn id(mut self, id: i32) -> Self {
-        self.query.insert("id%5B%5D", id.to_string());
+    pub fn id(mut self, id: TeamId) -> Self {
+        self.query.push("id[]", id);
         self
     }
-    pub fn ids(mut self, ids: &[i32]) -> Self {
-        self.query.insert("id%5B%5D", join(ids, ","));
+    pub fn ids(mut self, ids: &[TeamId]) -> Self {
+        self.query.extend("id[]", ids);
         self
     }
 
-    pub fn number(mut self, number: String) -> Self {
-        self.query.insert("number%5B%5D", number.to_string());
+    pub fn number(mut self, number: TeamNumber) -> Self {
+        self.query.push("number[]", number);
         self
     }
-    pub fn numbers(mut self, numbers: Vec<String>) -> Self {
-        self.query.insert("number%5B%5D", join(numbers, ","));
+    pub fn numbers(mut self, numbers: &[TeamNumber]) -> Self {
+        self.query.extend("number[]", numbers);
         self
     }
 
-    pub fn event(mut self, event: i32) -> Self {
-        self.query.insert("event%5B%5D", event.to_string());
+    pub fn event(mut self, event: EventId) -> Self {
+        self.query.push("event[]", event);
         self
     }
-    pub fn events(mut self, events: &[i32]) -> Self {
-        self.query.insert("event%5B%5D", join(events, ","));
+    pub fn events(mut self, events: &[EventId]) -> Self {
+        self.query.extend("event[]", events);
         self
     }
 
     pub fn registered(mut self, registered: bool) -> Self {
-        self.query.insert("registered", registered.to_string());
+        self.query.set("registered", registered);
         self
     }
 
-    pub fn program(mut self, program: i32) -> Self {
-        self.query.insert("program%5B%5D", program.to_string());
+    pub fn program(mut self, program: ProgramId) -> Self {
+        self.query.push("program[]", program);
         self
     }
-    pub fn programs(mut self, programs: &[i32]) -> Self {
-        self.query.insert("program%5B%5D", join(programs, ","));
+    pub fn programs(mut self, programs: &[ProgramId]) -> Self {
+        self.query.extend("program[]", programs);
         self
     }
 
     pub fn grade(mut self, grade: Grade) -> Self {
-        self.query.insert("grade%5B%5D", grade.to_string());
+        self.query.push("grade[]", grade);
         self
     }
     pub fn grades(mut self, grades: &[Grade]) -> Self {
-        self.query.insert("grade%5B%5D", join(grades, ","));
+        self.query.extend("grade[]", grades);
         self
     }
 
     pub fn country(mut self, country: String) -> Self {
-        self.query.insert("country%5B%5D", country.to_string());
+        self.query.push("country[]", country);
         self
     }
-    pub fn countries(mut self, countrys: &[String]) -> Self {
-        for c in countrys {
-            self.query.insert("country%5B%5D", c.to_string());
-        }
+    pub fn countries(mut self, countries: &[String]) -> Self {
+        self.query.extend("country[]", countries);
         self
     }
 
     pub fn my_teams(mut self, my_teams: bool) -> Self {
-        self.query.insert("myTeams", my_teams.to_string());
+        self.query.set("myTeams", my_teams);
         self
     }
 }
@@ -97,22 +114,29 @@ impl fake::Dummy<FakeTeamsQuery> for TeamsQuery {
         if rng.gen_bool(0.5) {
             q = q.ids(
                 &(0..rng.gen_range(1..10))
-                    .map(|_| rng.gen_range(0..99999))
-                    .collect::<Vec<i32>>(),
+                    .map(|_| TeamId(rng.gen_range(0..99999)))
+                    .collect::<Vec<TeamId>>(),
             );
         }
         if rng.gen_bool(0.5) {
             q = q.numbers(
-                (0..rng.gen_range(1..10))
-                    .map(|_| format!("{}{}", rng.gen_range(0..=9), rng.gen_range('A'..='Z')))
-                    .collect::<Vec<String>>(),
+                &(0..rng.gen_range(1..10))
+                    .filter_map(|_| {
+                        TeamNumber::new(format!(
+                            "{}{}",
+                            rng.gen_range(0..=9),
+                            rng.gen_range('A'..='Z')
+                        ))
+                        .ok()
+                    })
+                    .collect::<Vec<TeamNumber>>(),
             )
         }
         if rng.gen_bool(0.5) {
             q = q.events(
                 &(0..rng.gen_range(1..10))
-                    .map(|_| rng.gen())
-                    .collect::<Vec<i32>>(),
+                    .map(|_| EventId(rng.gen()))
+                    .collect::<Vec<EventId>>(),
             )
         }
         if rng.gen_bool(0.5) {
@@ -121,8 +145,8 @@ impl fake::Dummy<FakeTeamsQuery> for TeamsQuery {
         if rng.gen_bool(0.5) {
             q = q.programs(
                 &(0..rng.gen_range(1..10))
-                    .map(|_| rng.gen_range(0..60))
-                    .collect::<Vec<i32>>(),
+                    .map(|_| ProgramId(rng.gen_range(0..60)))
+                    .collect::<Vec<ProgramId>>(),
             );
         }
         if rng.gen_bool(0.5) {
@@ -158,9 +182,26 @@ impl fake::Dummy<FakeTeamsQuery> for TeamsQuery {
 }
 
 /// Queries for the RobotEvents `/teams/:id/events` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::TeamEventsQuery;
+/// use robotevents::schema::{EventLevel, SeasonId};
+///
+/// let query = TeamEventsQuery::new()
+///     .season(SeasonId(190))
+///     .level(EventLevel::World)
+///     .end("2025-05-01T00:00:00+00:00");
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?end=2025-05-01T00%3A00%3A00%2B00%3A00&level%5B%5D=World&season%5B%5D=190"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TeamEventsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(TeamEventsQuery);
@@ -171,47 +212,63 @@ impl TeamEventsQuery {
         Self::default()
     }
 
-    pub fn sku(mut self, sku: i32) -> Self {
-        self.query.insert("sku%5B%5D", sku.to_string());
+    pub fn sku(mut self, sku: EventSku) -> Self {
+        self.query.push("sku[]", sku);
         self
     }
-    pub fn skus(mut self, skus: &[i32]) -> Self {
-        self.query.insert("sku%5B%5D", join(skus, ","));
+    pub fn skus(mut self, skus: &[EventSku]) -> Self {
+        self.query.extend("sku[]", skus);
         self
     }
 
-    pub fn season(mut self, season: i32) -> Self {
-        self.query.insert("season%5B%5D", season.to_string());
+    pub fn season(mut self, season: SeasonId) -> Self {
+        self.query.push("season[]", season);
         self
     }
-    pub fn seasons(mut self, seasons: &[i32]) -> Self {
-        self.query.insert("season%5B%5D", join(seasons, ","));
+    pub fn seasons(mut self, seasons: &[SeasonId]) -> Self {
+        self.query.extend("season[]", seasons);
         self
     }
 
-    pub fn start(mut self, start: String) -> Self {
-        self.query.insert("start", start);
+    pub fn start(mut self, start: impl IntoQueryDate) -> Self {
+        self.query.set("start", start.into_query_date());
         self
     }
-    pub fn end(mut self, end: String) -> Self {
-        self.query.insert("end", end);
+    pub fn end(mut self, end: impl IntoQueryDate) -> Self {
+        self.query.set("end", end.into_query_date());
         self
     }
 
     pub fn level(mut self, level: EventLevel) -> Self {
-        self.query.insert("level%5B%5D", level.to_string());
+        self.query.push("level[]", level);
         self
     }
     pub fn levels(mut self, levels: &[EventLevel]) -> Self {
-        self.query.insert("level%5B%5D", join(levels, ","));
+        self.query.extend("level[]", levels);
         self
     }
 }
 
 /// Queries for the RobotEvents `/teams/:id/matches` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::TeamMatchesQuery;
+/// use robotevents::schema::{EventId, MatchRound};
+///
+/// let query = TeamMatchesQuery::new()
+///     .event(EventId(51488))
+///     .rounds(&[MatchRound::Semifinals, MatchRound::Finals]);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?event%5B%5D=51488&round%5B%5D=4&round%5B%5D=5"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TeamMatchesQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(TeamMatchesQuery);
@@ -222,59 +279,74 @@ impl TeamMatchesQuery {
         Self::default()
     }
 
-    pub fn event(mut self, event: i32) -> Self {
-        self.query.insert("event%5B%5D", event.to_string());
+    pub fn event(mut self, event: EventId) -> Self {
+        self.query.push("event[]", event);
         self
     }
-    pub fn events(mut self, events: &[i32]) -> Self {
-        self.query.insert("event%5B%5D", join(events, ","));
+    pub fn events(mut self, events: &[EventId]) -> Self {
+        self.query.extend("event[]", events);
         self
     }
 
-    pub fn season(mut self, season: i32) -> Self {
-        self.query.insert("season%5B%5D", season.to_string());
+    pub fn season(mut self, season: SeasonId) -> Self {
+        self.query.push("season[]", season);
         self
     }
-    pub fn seasons(mut self, seasons: &[i32]) -> Self {
-        self.query.insert("season%5B%5D", join(seasons, ","));
+    pub fn seasons(mut self, seasons: &[SeasonId]) -> Self {
+        self.query.extend("season[]", seasons);
         self
     }
 
     pub fn round(mut self, round: MatchRound) -> Self {
-        self.query.insert("round%5B%5D", (round as i32).to_string());
+        self.query.push("round[]", round.id());
         self
     }
     pub fn rounds(mut self, rounds: &[MatchRound]) -> Self {
-        self.query.insert(
-            "round%5B%5D",
-            join(rounds.iter().map(|round| round.clone() as i32), ","),
-        );
+        self.query
+            .extend("round[]", rounds.iter().map(|round| round.id()));
         self
     }
 
     pub fn instance(mut self, instance: i32) -> Self {
-        self.query.insert("instance%5B%5D", instance.to_string());
+        self.query.push("instance[]", instance);
         self
     }
     pub fn instances(mut self, instances: &[i32]) -> Self {
-        self.query.insert("instance%5B%5D", join(instances, ","));
+        self.query.extend("instance[]", instances);
         self
     }
 
     pub fn matchnum(mut self, matchnum: i32) -> Self {
-        self.query.insert("matchnum%5B%5D", matchnum.to_string());
+        self.query.push("matchnum[]", matchnum);
         self
     }
     pub fn matchnums(mut self, matchnums: &[i32]) -> Self {
-        self.query.insert("matchnum%5B%5D", join(matchnums, ","));
+        self.query.extend("matchnum[]", matchnums);
         self
     }
 }
 
 /// Queries for the RobotEvents `/teams/:id/rankings` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::TeamRankingsQuery;
+/// use robotevents::schema::{EventId, SeasonId};
+///
+/// let query = TeamRankingsQuery::new()
+///     .events(&[EventId(1), EventId(2)])
+///     .season(SeasonId(181))
+///     .rank(1);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?event%5B%5D=1&event%5B%5D=2&rank%5B%5D=1&season%5B%5D=181"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TeamRankingsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(TeamRankingsQuery);
@@ -285,38 +357,54 @@ impl TeamRankingsQuery {
         Self::default()
     }
 
-    pub fn event(mut self, event: i32) -> Self {
-        self.query.insert("event%5B%5D", event.to_string());
+    pub fn event(mut self, event: EventId) -> Self {
+        self.query.push("event[]", event);
         self
     }
-    pub fn events(mut self, events: &[i32]) -> Self {
-        self.query.insert("event%5B%5D", join(events, ","));
+    pub fn events(mut self, events: &[EventId]) -> Self {
+        self.query.extend("event[]", events);
         self
     }
 
     pub fn rank(mut self, rank: i32) -> Self {
-        self.query.insert("rank%5B%5D", rank.to_string());
+        self.query.push("rank[]", rank);
         self
     }
     pub fn ranks(mut self, ranks: &[i32]) -> Self {
-        self.query.insert("rank%5B%5D", join(ranks, ","));
+        self.query.extend("rank[]", ranks);
         self
     }
 
-    pub fn season(mut self, season: i32) -> Self {
-        self.query.insert("season%5B%5D", season.to_string());
+    pub fn season(mut self, season: SeasonId) -> Self {
+        self.query.push("season[]", season);
         self
     }
-    pub fn seasons(mut self, seasons: &[i32]) -> Self {
-        self.query.insert("season%5B%5D", join(seasons, ","));
+    pub fn seasons(mut self, seasons: &[SeasonId]) -> Self {
+        self.query.extend("season[]", seasons);
         self
     }
 }
 
 /// Queries for the RobotEvents `/teams/:id/skills` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::TeamSkillsQuery;
+/// use robotevents::schema::{SeasonId, SkillType};
+///
+/// let query = TeamSkillsQuery::new()
+///     .skill_type(SkillType::Programming)
+///     .seasons(&[SeasonId(181), SeasonId(190)]);
+///
+/// assert_eq!(
+///     query.to_string(),
+///     "?season%5B%5D=181&season%5B%5D=190&type%5B%5D=programming"
+/// );
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TeamSkillsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(TeamSkillsQuery);
@@ -327,38 +415,49 @@ impl TeamSkillsQuery {
         Self::default()
     }
 
-    pub fn event(mut self, event: i32) -> Self {
-        self.query.insert("event%5B%5D", event.to_string());
+    pub fn event(mut self, event: EventId) -> Self {
+        self.query.push("event[]", event);
         self
     }
-    pub fn events(mut self, events: &[i32]) -> Self {
-        self.query.insert("event%5B%5D", join(events, ","));
+    pub fn events(mut self, events: &[EventId]) -> Self {
+        self.query.extend("event[]", events);
         self
     }
 
     pub fn skill_type(mut self, skill_type: SkillType) -> Self {
-        self.query.insert("type%5B%5D", skill_type.to_string());
+        self.query.push("type[]", skill_type);
         self
     }
     pub fn skill_types(mut self, skill_types: &[SkillType]) -> Self {
-        self.query.insert("type%5B%5D", join(skill_types, ","));
+        self.query.extend("type[]", skill_types);
         self
     }
 
-    pub fn season(mut self, season: i32) -> Self {
-        self.query.insert("season%5B%5D", season.to_string());
+    pub fn season(mut self, season: SeasonId) -> Self {
+        self.query.push("season[]", season);
         self
     }
-    pub fn seasons(mut self, seasons: &[i32]) -> Self {
-        self.query.insert("season%5B%5D", join(seasons, ","));
+    pub fn seasons(mut self, seasons: &[SeasonId]) -> Self {
+        self.query.extend("season[]", seasons);
         self
     }
 }
 
 /// Queries for the RobotEvents `/teams/:id/awards` endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::query::TeamAwardsQuery;
+/// use robotevents::schema::EventId;
+///
+/// let query = TeamAwardsQuery::new().event(EventId(51488));
+///
+/// assert_eq!(query.to_string(), "?event%5B%5D=51488");
+/// ```
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct TeamAwardsQuery {
-    query: HashMap<&'static str, String>,
+    query: QueryParams,
 }
 
 impl_paginated_query!(TeamAwardsQuery);
@@ -369,21 +468,21 @@ impl TeamAwardsQuery {
         Self::default()
     }
 
-    pub fn event(mut self, event: i32) -> Self {
-        self.query.insert("event%5B%5D", event.to_string());
+    pub fn event(mut self, event: EventId) -> Self {
+        self.query.push("event[]", event);
         self
     }
-    pub fn events(mut self, events: &[i32]) -> Self {
-        self.query.insert("event%5B%5D", join(events, ","));
+    pub fn events(mut self, events: &[EventId]) -> Self {
+        self.query.extend("event[]", events);
         self
     }
 
-    pub fn season(mut self, season: i32) -> Self {
-        self.query.insert("season%5B%5D", season.to_string());
+    pub fn season(mut self, season: SeasonId) -> Self {
+        self.query.push("season[]", season);
         self
     }
-    pub fn seasons(mut self, seasons: &[i32]) -> Self {
-        self.query.insert("season%5B%5D", join(seasons, ","));
+    pub fn seasons(mut self, seasons: &[SeasonId]) -> Self {
+        self.query.extend("season[]", seasons);
         self
     }
 }
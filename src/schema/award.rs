@@ -1,19 +1,24 @@
-use super::IdInfo;
+use super::{DivisionId, EventId, IdInfo, TeamId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TeamAwardWinner {
-    division: IdInfo,
-    team: IdInfo,
+    division: IdInfo<DivisionId>,
+    team: IdInfo<TeamId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum AwardClassification {
     Champion,
     Finalist,
     Semifinalist,
     Quarterfinalist,
+
+    /// An award classification not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl std::fmt::Display for AwardClassification {
@@ -23,15 +28,21 @@ impl std::fmt::Display for AwardClassification {
             Self::Finalist => "Finalist",
             Self::Semifinalist => "Semifinalist",
             Self::Quarterfinalist => "Quarterfinalist",
+            Self::Unknown(value) => value,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum AwardDesignation {
     Tournament,
     Division,
+
+    /// An award designation not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl std::fmt::Display for AwardDesignation {
@@ -39,6 +50,7 @@ impl std::fmt::Display for AwardDesignation {
         f.write_str(match self {
             Self::Tournament => "Tournament",
             Self::Division => "Division",
+            Self::Unknown(value) => value,
         })
     }
 }
@@ -47,7 +59,7 @@ impl std::fmt::Display for AwardDesignation {
 #[serde(rename_all = "camelCase")]
 pub struct Award {
     pub id: i32,
-    pub event: IdInfo,
+    pub event: IdInfo<EventId>,
     pub order: i32,
     pub title: String,
     pub qualifications: Vec<String>,
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::schema::{IdInfo, Location};
+use crate::schema::{DivisionId, EventId, IdInfo, Location, ProgramId, SeasonId};
 
 use serde::{Deserialize, Serialize};
 
@@ -18,12 +18,13 @@ use crate::{
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Division {
-    pub id: i32,
+    pub id: DivisionId,
     pub name: String,
     pub order: i32,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum EventLevel {
     World,
     National,
@@ -31,6 +32,10 @@ pub enum EventLevel {
     State,
     Signature,
     Other,
+
+    /// An event level not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl std::fmt::Display for EventLevel {
@@ -42,17 +47,23 @@ impl std::fmt::Display for EventLevel {
             Self::State => "State",
             Self::Signature => "Signature",
             Self::Other => "Other",
+            Self::Unknown(value) => value,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum EventType {
     Tournament,
     League,
     Workshop,
     Virtual,
+
+    /// An event type not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl std::fmt::Display for EventType {
@@ -62,19 +73,20 @@ impl std::fmt::Display for EventType {
             Self::League => "League",
             Self::Workshop => "Workshop",
             Self::Virtual => "Virtual",
+            Self::Unknown(value) => value,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Event {
-    pub id: i32,
+    pub id: EventId,
     pub sku: String,
     pub name: String,
     pub start: String,
     pub end: String,
-    pub season: IdInfo,
-    pub program: IdInfo,
+    pub season: IdInfo<SeasonId>,
+    pub program: IdInfo<ProgramId>,
     pub location: Location,
     pub locations: HashMap<String, Location>,
     pub divisions: Vec<Division>,
@@ -84,6 +96,171 @@ pub struct Event {
     pub event_type: Option<EventType>,
 }
 
+impl Event {
+    /// Parses the event's [`sku`](Self::sku) into an [`EventSku`].
+    pub fn event_sku(&self) -> Result<EventSku, EventSkuError> {
+        EventSku::new(&self.sku)
+    }
+
+    /// Parses the event's [`start`](Self::start) timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn start_time(&self) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+        super::parse_timestamp(&self.start)
+    }
+
+    /// Parses the event's [`end`](Self::end) timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn end_time(&self) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+        super::parse_timestamp(&self.end)
+    }
+}
+
+/// An event's SKU, such as `RE-V5RC-24-1234`.
+///
+/// SKUs are made up of the `RE` prefix, the code of the event's program, the last two digits
+/// of the year its season started in and a serial number. They are normalized when parsed, so
+/// `" re-v5rc-24-1234 "` and `"RE-V5RC-24-1234"` are the same SKU.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::schema::EventSku;
+///
+/// let sku: EventSku = "re-vrc-23-1234".parse()?;
+///
+/// assert_eq!(sku.program_code(), "VRC");
+/// assert_eq!(sku.season_year(), 2023);
+/// assert_eq!(sku.serial(), 1234);
+/// assert_eq!(sku.to_string(), "RE-VRC-23-1234");
+///
+/// assert!("RE-VRC-1234".parse::<EventSku>().is_err());
+/// # Ok::<(), robotevents::schema::EventSkuError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct EventSku(String);
+
+impl EventSku {
+    /// Parses and normalizes an event SKU.
+    pub fn new(sku: impl AsRef<str>) -> Result<Self, EventSkuError> {
+        let sku = sku.as_ref().trim().to_ascii_uppercase();
+        let invalid = || EventSkuError { sku: sku.clone() };
+
+        let parts: Vec<&str> = sku.split('-').collect();
+        let [prefix, program, year, serial] = parts.as_slice() else {
+            return Err(invalid());
+        };
+
+        let valid = *prefix == "RE"
+            && !program.is_empty()
+            && program.chars().all(|c| c.is_ascii_alphanumeric())
+            && year.len() == 2
+            && year.chars().all(|c| c.is_ascii_digit())
+            && !serial.is_empty()
+            && serial.len() <= 9
+            && serial.chars().all(|c| c.is_ascii_digit());
+
+        if valid {
+            Ok(Self(sku))
+        } else {
+            Err(invalid())
+        }
+    }
+
+    /// The normalized SKU.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The code of the event's program, such as `V5RC` or `VIQRC`.
+    ///
+    /// Older SKUs use older program codes, such as `VRC` and `VIQC`.
+    pub fn program_code(&self) -> &str {
+        self.parts().1
+    }
+
+    /// The year the event's season started in, such as `2024` for the 2024-2025 season.
+    pub fn season_year(&self) -> u16 {
+        2000 + self.parts().2.parse::<u16>().unwrap_or_default()
+    }
+
+    /// The serial number of the event within its program and season.
+    pub fn serial(&self) -> u32 {
+        self.parts().3.parse().unwrap_or_default()
+    }
+
+    fn parts(&self) -> (&str, &str, &str, &str) {
+        let mut parts = self.0.splitn(4, '-');
+        let mut next = || parts.next().unwrap_or_default();
+        (next(), next(), next(), next())
+    }
+}
+
+impl std::str::FromStr for EventSku {
+    type Err = EventSkuError;
+
+    fn from_str(sku: &str) -> Result<Self, Self::Err> {
+        Self::new(sku)
+    }
+}
+
+impl TryFrom<String> for EventSku {
+    type Error = EventSkuError;
+
+    fn try_from(sku: String) -> Result<Self, Self::Error> {
+        Self::new(sku)
+    }
+}
+
+impl From<EventSku> for String {
+    fn from(sku: EventSku) -> Self {
+        sku.0
+    }
+}
+
+impl AsRef<str> for EventSku {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for EventSku {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for EventSku {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl std::fmt::Display for EventSku {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An error returned when a string isn't a valid [`EventSku`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSkuError {
+    /// The SKU that failed to parse, after normalization.
+    pub sku: String,
+}
+
+impl std::fmt::Display for EventSkuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid event SKU {:?}, expected a SKU like RE-V5RC-24-1234",
+            self.sku
+        )
+    }
+}
+
+impl std::error::Error for EventSkuError {}
+
 #[cfg(feature = "client")]
 impl Event {
     pub async fn teams(
@@ -109,7 +286,7 @@ impl Event {
     }
     pub async fn division_matches(
         &self,
-        division_id: i32,
+        division_id: DivisionId,
         client: &RobotEvents,
         query: DivisionMatchesQuery,
     ) -> Result<PaginatedResponse<Match>, error::Error> {
@@ -119,7 +296,7 @@ impl Event {
     }
     pub async fn division_finalist_rankings(
         &self,
-        division_id: i32,
+        division_id: DivisionId,
         client: &RobotEvents,
         query: DivisionRankingsQuery,
     ) -> Result<PaginatedResponse<Ranking>, error::Error> {
@@ -129,7 +306,7 @@ impl Event {
     }
     pub async fn division_rankings(
         &self,
-        division_id: i32,
+        division_id: DivisionId,
         client: &RobotEvents,
         query: DivisionRankingsQuery,
     ) -> Result<PaginatedResponse<Ranking>, error::Error> {
@@ -138,3 +315,79 @@ impl Event {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sku(sku: &str) -> EventSku {
+        EventSku::new(sku).unwrap()
+    }
+
+    #[test]
+    fn skus_across_programs() {
+        let skus = [
+            ("RE-V5RC-24-1234", "V5RC", 2024, 1234),
+            ("RE-VIQRC-24-5678", "VIQRC", 2024, 5678),
+            ("RE-VEXU-24-9", "VEXU", 2024, 9),
+            ("RE-VRC-23-1234", "VRC", 2023, 1234),
+            ("RE-VIQC-19-123456789", "VIQC", 2019, 123456789),
+        ];
+
+        for (value, program, year, serial) in skus {
+            let parsed = sku(value);
+            assert_eq!(parsed, value);
+            assert_eq!(parsed.program_code(), program);
+            assert_eq!(parsed.season_year(), year);
+            assert_eq!(parsed.serial(), serial);
+        }
+    }
+
+    #[test]
+    fn skus_are_normalized() {
+        assert_eq!(sku(" re-v5rc-24-1234\n"), "RE-V5RC-24-1234");
+        assert_eq!(sku("re-v5rc-24-1234"), sku("RE-V5RC-24-1234"));
+        assert_eq!(
+            EventSku::new("re-v5rc-24"),
+            Err(EventSkuError {
+                sku: "RE-V5RC-24".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn wrong_prefixes() {
+        for invalid in ["XX-V5RC-24-1234", "R-V5RC-24-1234", "-V5RC-24-1234"] {
+            assert!(EventSku::new(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn missing_or_extra_segments() {
+        for invalid in [
+            "",
+            "RE",
+            "RE-V5RC-1234",
+            "RE--24-1234",
+            "RE-V5RC--1234",
+            "RE-V5RC-24-",
+            "RE-V5RC-24-1234-5",
+        ] {
+            assert!(EventSku::new(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_years_and_serials() {
+        for invalid in [
+            "RE-V5RC-2X-1234",
+            "RE-V5RC-2024-1234",
+            "RE-V5RC-4-1234",
+            "RE-V5RC-24-12A4",
+            "RE-V5RC-24-1234567890",
+            "RE-V5 RC-24-1234",
+        ] {
+            assert!(EventSku::new(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// Defines a newtype wrapper around a numeric RobotEvents ID.
+///
+/// The wrapper (de)serializes transparently, so the wire format is a plain integer.
+macro_rules! id_type {
+    ( $(#[$meta:meta])* $name:ident ) => {
+        $(#[$meta])*
+        #[derive(
+            Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub i32);
+
+        impl From<i32> for $name {
+            fn from(id: i32) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+id_type!(
+    /// The ID of a [`Team`](super::Team).
+    TeamId
+);
+id_type!(
+    /// The ID of an [`Event`](super::Event).
+    EventId
+);
+id_type!(
+    /// The ID of a [`Season`](super::Season).
+    SeasonId
+);
+id_type!(
+    /// The ID of a [`Program`](super::Program).
+    ProgramId
+);
+id_type!(
+    /// The ID of a [`Division`](super::Division) within an event.
+    DivisionId
+);
+id_type!(
+    /// The ID of a [`Match`](super::Match).
+    MatchId
+);
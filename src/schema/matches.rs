@@ -1,12 +1,12 @@
-use super::IdInfo;
+use super::{DivisionId, EventId, IdInfo, MatchId, TeamId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Match {
-    pub id: i32,
-    pub event: IdInfo,
-    pub division: IdInfo,
-    pub round: i32,
+    pub id: MatchId,
+    pub event: IdInfo<EventId>,
+    pub division: IdInfo<DivisionId>,
+    pub round: MatchRound,
     pub instance: i32,
     pub matchnum: i32,
     pub scheduled: Option<String>,
@@ -17,11 +17,163 @@ pub struct Match {
     pub alliances: Vec<Alliance>,
 }
 
+impl Match {
+    /// Parses the match's [`scheduled`](Self::scheduled) timestamp, if it has one.
+    #[cfg(feature = "chrono")]
+    pub fn scheduled_time(
+        &self,
+    ) -> Result<Option<chrono::DateTime<chrono::FixedOffset>>, chrono::ParseError> {
+        self.scheduled
+            .as_deref()
+            .map(super::parse_timestamp)
+            .transpose()
+    }
+
+    /// Parses the match's [`started`](Self::started) timestamp, if it has one.
+    #[cfg(feature = "chrono")]
+    pub fn started_time(
+        &self,
+    ) -> Result<Option<chrono::DateTime<chrono::FixedOffset>>, chrono::ParseError> {
+        self.started
+            .as_deref()
+            .map(super::parse_timestamp)
+            .transpose()
+    }
+
+    /// The red alliance, if the match has one.
+    pub fn red(&self) -> Option<&Alliance> {
+        self.alliance(AllianceColor::Red)
+    }
+
+    /// The blue alliance, if the match has one.
+    pub fn blue(&self) -> Option<&Alliance> {
+        self.alliance(AllianceColor::Blue)
+    }
+
+    fn alliance(&self, color: AllianceColor) -> Option<&Alliance> {
+        self.alliances
+            .iter()
+            .find(|alliance| alliance.color == color)
+    }
+
+    /// The alliance a team played on in this match.
+    pub fn alliance_of(&self, team_id: TeamId) -> Option<&Alliance> {
+        self.alliances
+            .iter()
+            .find(|alliance| alliance.has_team(team_id))
+    }
+
+    /// The alliance a team played against in this match.
+    ///
+    /// Returns `None` if the team didn't play in the match, or if the match only has one
+    /// alliance (as in VIQRC teamwork matches).
+    pub fn opponent_of(&self, team_id: TeamId) -> Option<&Alliance> {
+        self.alliance_of(team_id)?;
+        self.alliances
+            .iter()
+            .find(|alliance| !alliance.has_team(team_id))
+    }
+
+    /// The color of the alliance that won the match.
+    ///
+    /// Returns `None` if the match hasn't been scored, ended in a tie or doesn't have both a
+    /// red and a blue alliance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use robotevents::schema::Match;
+    ///
+    /// fn describe(m: &Match) -> String {
+    ///     match (m.winner(), m.margin()) {
+    ///         (Some(color), Some(margin)) => format!("{color} won {} by {margin}", m.display_name()),
+    ///         (None, Some(_)) => format!("{} was a tie", m.display_name()),
+    ///         _ => format!("{} hasn't been scored", m.display_name()),
+    ///     }
+    /// }
+    /// ```
+    pub fn winner(&self) -> Option<AllianceColor> {
+        let (red, blue) = self.scores()?;
+        match red.cmp(&blue) {
+            std::cmp::Ordering::Greater => Some(AllianceColor::Red),
+            std::cmp::Ordering::Less => Some(AllianceColor::Blue),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Whether the match was scored and ended in a tie.
+    pub fn is_tie(&self) -> bool {
+        self.scores().is_some_and(|(red, blue)| red == blue)
+    }
+
+    /// The difference between the winning and losing alliances' scores, or `0` for a tie.
+    ///
+    /// Returns `None` if the match hasn't been scored or doesn't have both a red and a blue
+    /// alliance.
+    pub fn margin(&self) -> Option<i32> {
+        let (red, blue) = self.scores()?;
+        Some((red - blue).abs())
+    }
+
+    /// The red and blue alliances' scores, if the match has been scored.
+    fn scores(&self) -> Option<(i32, i32)> {
+        if !self.scored {
+            return None;
+        }
+        Some((self.red()?.score, self.blue()?.score))
+    }
+
+    /// Whether the match is part of an elimination bracket (round of 16 through finals).
+    pub fn is_elimination(&self) -> bool {
+        matches!(
+            self.round,
+            MatchRound::RoundOf16
+                | MatchRound::Quarterfinals
+                | MatchRound::Semifinals
+                | MatchRound::Finals
+        )
+    }
+
+    /// A short name for the match, as shown on RobotEvents.
+    ///
+    /// Practice and qualification matches are named by their number, such as `Q12`.
+    /// Elimination matches are named by their instance and number, such as `SF 2-1` for the
+    /// first match between the alliances in the second semifinal. Matches in rounds unknown
+    /// to this version of the crate use [`Match::name`].
+    pub fn display_name(&self) -> String {
+        let (instance, matchnum) = (self.instance, self.matchnum);
+        match self.round {
+            MatchRound::Practice => format!("P{matchnum}"),
+            MatchRound::Qualification => format!("Q{matchnum}"),
+            MatchRound::RoundOf16 => format!("R16 {instance}-{matchnum}"),
+            MatchRound::Quarterfinals => format!("QF {instance}-{matchnum}"),
+            MatchRound::Semifinals => format!("SF {instance}-{matchnum}"),
+            MatchRound::Finals => format!("F {instance}-{matchnum}"),
+            MatchRound::Unknown(_) => self.name.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum AllianceColor {
     Red,
     Blue,
+
+    /// An alliance color not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl std::fmt::Display for AllianceColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Red => "red",
+            Self::Blue => "blue",
+            Self::Unknown(value) => value,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -31,31 +183,318 @@ pub struct Alliance {
     pub teams: Vec<AllianceTeam>,
 }
 
+impl Alliance {
+    /// Whether a team played on this alliance.
+    pub fn has_team(&self, team_id: TeamId) -> bool {
+        self.teams.iter().any(|team| team.team.id == team_id)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AllianceTeam {
-    pub team: IdInfo,
+    pub team: IdInfo<TeamId>,
     pub sitting: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The round of a competition a match is played in.
+///
+/// Rounds are (de)serialized as the numeric IDs used by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "i32", into = "i32")]
+#[non_exhaustive]
 pub enum MatchRound {
-    Practice = 1,
-    Qualification = 2,
-    Quarterfinals = 3,
-    Semifinals = 4,
-    Finals = 5,
-    RoundOf16 = 6,
+    Practice,
+    Qualification,
+    Quarterfinals,
+    Semifinals,
+    Finals,
+    RoundOf16,
+
+    /// A round not known to this version of the crate, kept as its ID.
+    Unknown(i32),
+}
+
+impl MatchRound {
+    /// The round's numeric ID in the API.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use robotevents::schema::MatchRound;
+    ///
+    /// assert_eq!(MatchRound::Qualification.id(), 2);
+    /// assert_eq!(MatchRound::from(6), MatchRound::RoundOf16);
+    /// assert_eq!(MatchRound::from(15), MatchRound::Unknown(15));
+    /// ```
+    pub fn id(self) -> i32 {
+        match self {
+            Self::Practice => 1,
+            Self::Qualification => 2,
+            Self::Quarterfinals => 3,
+            Self::Semifinals => 4,
+            Self::Finals => 5,
+            Self::RoundOf16 => 6,
+            Self::Unknown(id) => id,
+        }
+    }
+}
+
+impl From<i32> for MatchRound {
+    fn from(id: i32) -> Self {
+        match id {
+            1 => Self::Practice,
+            2 => Self::Qualification,
+            3 => Self::Quarterfinals,
+            4 => Self::Semifinals,
+            5 => Self::Finals,
+            6 => Self::RoundOf16,
+            id => Self::Unknown(id),
+        }
+    }
+}
+
+impl From<MatchRound> for i32 {
+    fn from(round: MatchRound) -> Self {
+        round.id()
+    }
 }
 
 impl std::fmt::Display for MatchRound {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.write_str(match self {
-			Self::Practice => "Practice",
-			Self::Qualification => "Qualification",
-			Self::Quarterfinals => "Quarterfinals",
-			Self::Semifinals => "Semifinals",
-			Self::Finals => "Finals",
-			Self::RoundOf16 => "Round of 16",
+            Self::Practice => "Practice",
+            Self::Qualification => "Qualification",
+            Self::Quarterfinals => "Quarterfinals",
+            Self::Semifinals => "Semifinals",
+            Self::Finals => "Finals",
+            Self::RoundOf16 => "Round of 16",
+            Self::Unknown(id) => return write!(f, "Round {id}"),
         })
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Builds [`Match`]es for tests. Matches are scored and have no alliances until they're
+    /// added.
+    pub(crate) struct MatchBuilder(Match);
+
+    impl MatchBuilder {
+        pub(crate) fn new(round: MatchRound, instance: i32, matchnum: i32) -> Self {
+            Self(Match {
+                id: MatchId(matchnum),
+                event: IdInfo {
+                    id: EventId(1),
+                    name: "Event".to_owned(),
+                    code: None,
+                },
+                division: IdInfo {
+                    id: DivisionId(1),
+                    name: "Division".to_owned(),
+                    code: None,
+                },
+                round,
+                instance,
+                matchnum,
+                scheduled: None,
+                started: None,
+                field: None,
+                scored: true,
+                name: format!("Match {matchnum}"),
+                alliances: Vec::new(),
+            })
+        }
+
+        pub(crate) fn qualification(matchnum: i32) -> Self {
+            Self::new(MatchRound::Qualification, 1, matchnum)
+        }
+
+        pub(crate) fn id(mut self, id: i32) -> Self {
+            self.0.id = MatchId(id);
+            self
+        }
+
+        pub(crate) fn event(mut self, event: i32, division: i32) -> Self {
+            self.0.event.id = EventId(event);
+            self.0.division.id = DivisionId(division);
+            self
+        }
+
+        pub(crate) fn started(mut self, started: &str) -> Self {
+            self.0.started = Some(started.to_owned());
+            self
+        }
+
+        pub(crate) fn unscored(mut self) -> Self {
+            self.0.scored = false;
+            self
+        }
+
+        pub(crate) fn alliance(mut self, color: AllianceColor, teams: &[i32], score: i32) -> Self {
+            let teams = teams
+                .iter()
+                .map(|&id| AllianceTeam {
+                    team: IdInfo {
+                        id: TeamId(id),
+                        name: format!("{id}A"),
+                        code: None,
+                    },
+                    sitting: false,
+                })
+                .collect();
+            self.0.alliances.push(Alliance {
+                color,
+                score,
+                teams,
+            });
+            self
+        }
+
+        pub(crate) fn red(self, teams: &[i32], score: i32) -> Self {
+            self.alliance(AllianceColor::Red, teams, score)
+        }
+
+        pub(crate) fn blue(self, teams: &[i32], score: i32) -> Self {
+            self.alliance(AllianceColor::Blue, teams, score)
+        }
+
+        /// Marks a team as sitting out the match.
+        pub(crate) fn sitting(mut self, team: i32) -> Self {
+            for alliance in &mut self.0.alliances {
+                for alliance_team in &mut alliance.teams {
+                    if alliance_team.team.id == TeamId(team) {
+                        alliance_team.sitting = true;
+                    }
+                }
+            }
+            self
+        }
+
+        pub(crate) fn build(self) -> Match {
+            self.0
+        }
+    }
+
+    /// A scored qualification match between red and blue alliances.
+    pub(crate) fn qualification(red: (&[i32], i32), blue: (&[i32], i32)) -> Match {
+        MatchBuilder::qualification(1)
+            .red(red.0, red.1)
+            .blue(blue.0, blue.1)
+            .build()
+    }
+
+    fn colors(alliance: Option<&Alliance>) -> Option<AllianceColor> {
+        alliance.map(|alliance| alliance.color.clone())
+    }
+
+    #[test]
+    fn alliances_by_color() {
+        // Alliances are found by color, not by their order.
+        let m = MatchBuilder::qualification(1)
+            .blue(&[3, 4], 40)
+            .red(&[1, 2], 50)
+            .build();
+
+        assert_eq!(m.red().map(|a| a.score), Some(50));
+        assert_eq!(m.blue().map(|a| a.score), Some(40));
+
+        let red_only = MatchBuilder::qualification(1).red(&[1, 2], 50).build();
+        assert!(red_only.red().is_some());
+        assert!(red_only.blue().is_none());
+    }
+
+    #[test]
+    fn alliances_by_team() {
+        let m = qualification((&[1, 2], 50), (&[3, 4], 40));
+
+        assert_eq!(colors(m.alliance_of(TeamId(2))), Some(AllianceColor::Red));
+        assert_eq!(colors(m.opponent_of(TeamId(2))), Some(AllianceColor::Blue));
+        assert_eq!(colors(m.alliance_of(TeamId(3))), Some(AllianceColor::Blue));
+        assert_eq!(colors(m.opponent_of(TeamId(3))), Some(AllianceColor::Red));
+        assert_eq!(m.alliance_of(TeamId(9)), None);
+        assert_eq!(m.opponent_of(TeamId(9)), None);
+    }
+
+    #[test]
+    fn single_alliance_matches_have_no_opponent() {
+        let m = MatchBuilder::qualification(1).red(&[1, 2], 50).build();
+
+        assert_eq!(colors(m.alliance_of(TeamId(1))), Some(AllianceColor::Red));
+        assert_eq!(m.opponent_of(TeamId(1)), None);
+        assert_eq!(m.winner(), None);
+        assert_eq!(m.margin(), None);
+        assert!(!m.is_tie());
+    }
+
+    #[test]
+    fn winners_and_margins() {
+        let red_win = qualification((&[1, 2], 50), (&[3, 4], 40));
+        assert_eq!(red_win.winner(), Some(AllianceColor::Red));
+        assert_eq!(red_win.margin(), Some(10));
+        assert!(!red_win.is_tie());
+
+        let blue_win = qualification((&[1, 2], 30), (&[3, 4], 40));
+        assert_eq!(blue_win.winner(), Some(AllianceColor::Blue));
+        assert_eq!(blue_win.margin(), Some(10));
+
+        let tie = qualification((&[1, 2], 40), (&[3, 4], 40));
+        assert_eq!(tie.winner(), None);
+        assert_eq!(tie.margin(), Some(0));
+        assert!(tie.is_tie());
+    }
+
+    #[test]
+    fn unscored_matches_have_no_result() {
+        let m = MatchBuilder::qualification(1)
+            .red(&[1, 2], 0)
+            .blue(&[3, 4], 0)
+            .unscored()
+            .build();
+
+        assert_eq!(m.winner(), None);
+        assert_eq!(m.margin(), None);
+        assert!(!m.is_tie());
+    }
+
+    #[test]
+    fn elimination_rounds() {
+        let eliminations = [
+            MatchRound::RoundOf16,
+            MatchRound::Quarterfinals,
+            MatchRound::Semifinals,
+            MatchRound::Finals,
+        ];
+        for round in eliminations {
+            assert!(MatchBuilder::new(round, 1, 1).build().is_elimination());
+        }
+
+        let others = [
+            MatchRound::Practice,
+            MatchRound::Qualification,
+            MatchRound::Unknown(15),
+        ];
+        for round in others {
+            assert!(!MatchBuilder::new(round, 1, 1).build().is_elimination());
+        }
+    }
+
+    #[test]
+    fn display_names() {
+        let name = |round, instance, matchnum| {
+            MatchBuilder::new(round, instance, matchnum)
+                .build()
+                .display_name()
+        };
+
+        assert_eq!(name(MatchRound::Practice, 1, 3), "P3");
+        assert_eq!(name(MatchRound::Qualification, 1, 12), "Q12");
+        assert_eq!(name(MatchRound::RoundOf16, 4, 1), "R16 4-1");
+        assert_eq!(name(MatchRound::Quarterfinals, 3, 1), "QF 3-1");
+        assert_eq!(name(MatchRound::Semifinals, 2, 1), "SF 2-1");
+        assert_eq!(name(MatchRound::Finals, 1, 2), "F 1-2");
+        // Unknown rounds fall back to the name sent by the API.
+        assert_eq!(name(MatchRound::Unknown(15), 1, 7), "Match 7");
+    }
+}
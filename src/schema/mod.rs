@@ -1,5 +1,6 @@
 pub mod award;
 pub mod event;
+pub mod id;
 pub mod location;
 pub mod matches;
 pub mod program;
@@ -7,9 +8,11 @@ pub mod ranking;
 pub mod season;
 pub mod skill;
 pub mod team;
+pub mod v1;
 
 pub use award::*;
 pub use event::*;
+pub use id::*;
 pub use location::*;
 pub use matches::*;
 pub use program::*;
@@ -19,7 +22,7 @@ pub use skill::*;
 pub use team::*;
 
 #[cfg(feature = "client")]
-use crate::{client::error, RobotEvents, V2_API_BASE};
+use crate::{client::error, RobotEvents};
 #[cfg(feature = "client")]
 use serde::de::DeserializeOwned;
 
@@ -46,6 +49,27 @@ pub struct PageMeta {
 pub struct PaginatedResponse<T> {
     pub meta: PageMeta,
     pub data: Vec<T>,
+
+    /// Records that failed to deserialize and were left out of `data`.
+    ///
+    /// Only populated when the client was built with
+    /// [`RobotEventsBuilder::skip_invalid_records`](crate::RobotEventsBuilder::skip_invalid_records).
+    #[serde(skip)]
+    pub skipped: Vec<SkippedRecord>,
+}
+
+/// A record from a paginated response that could not be deserialized.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedRecord {
+    /// The record's position in the page's `data` array.
+    pub index: usize,
+    /// The path to the field that failed to deserialize, e.g. `data[17].grade`.
+    pub path: String,
+    /// A description of why deserialization failed.
+    pub error: String,
+    /// The record as returned by the API.
+    pub raw: serde_json::Value,
 }
 
 #[cfg(feature = "client")]
@@ -55,16 +79,7 @@ impl<T: DeserializeOwned> PaginatedResponse<T> {
         robotevents: &RobotEvents,
     ) -> Option<Result<PaginatedResponse<T>, error::Error>> {
         if let Some(url) = &self.meta.prev_page_url {
-            match robotevents
-                .request(url.trim_start_matches(V2_API_BASE))
-                .await
-            {
-                Ok(response) => match response.json().await {
-                    Ok(json) => Some(Ok(json)),
-                    Err(error) => Some(Err(error.into())),
-                },
-                Err(error) => Some(Err(error)),
-            }
+            Some(robotevents.request_page_url(url).await)
         } else {
             None
         }
@@ -75,16 +90,7 @@ impl<T: DeserializeOwned> PaginatedResponse<T> {
         robotevents: &RobotEvents,
     ) -> Option<Result<PaginatedResponse<T>, error::Error>> {
         if let Some(url) = &self.meta.next_page_url {
-            match robotevents
-                .request(url.trim_start_matches(V2_API_BASE))
-                .await
-            {
-                Ok(response) => match response.json().await {
-                    Ok(json) => Some(Ok(json)),
-                    Err(error) => Some(Err(error.into())),
-                },
-                Err(error) => Some(Err(error)),
-            }
+            Some(robotevents.request_page_url(url).await)
         } else {
             None
         }
@@ -95,29 +101,103 @@ impl<T: DeserializeOwned> PaginatedResponse<T> {
         robotevents: &RobotEvents,
     ) -> Result<PaginatedResponse<T>, error::Error> {
         robotevents
-            .request(self.meta.first_page_url.trim_start_matches(V2_API_BASE))
-            .await?
-            .json()
+            .request_page_url(&self.meta.first_page_url)
             .await
-            .map_err(|e| e.into())
     }
 
     pub async fn last_page(
         &self,
         robotevents: &RobotEvents,
     ) -> Result<PaginatedResponse<T>, error::Error> {
-        robotevents
-            .request(self.meta.last_page_url.trim_start_matches(V2_API_BASE))
-            .await?
-            .json()
-            .await
-            .map_err(|e| e.into())
+        robotevents.request_page_url(&self.meta.last_page_url).await
     }
 }
 
+/// Parses an RFC 3339 timestamp sent by the API, such as [`Event::start`].
+#[cfg(feature = "chrono")]
+fn parse_timestamp(
+    timestamp: &str,
+) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+}
+
+/// A reference to another RobotEvents resource, identified by an ID of type `Id`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct IdInfo {
-    pub id: i32,
+pub struct IdInfo<Id> {
+    pub id: Id,
     pub name: String,
     pub code: Option<String>,
 }
+
+#[cfg(all(test, any(feature = "chrono", feature = "client")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamps_parse_with_their_offset() {
+        let time = parse_timestamp("2024-04-25T08:00:00-05:00").unwrap();
+
+        assert_eq!(time.offset().local_minus_utc(), -5 * 60 * 60);
+        assert_eq!(time.to_rfc3339(), "2024-04-25T08:00:00-05:00");
+        assert!(parse_timestamp("April 25th").is_err());
+    }
+
+    /// Checks that `json` deserializes to `expected` and serializes back unchanged.
+    #[cfg(feature = "client")]
+    fn assert_round_trip<T>(json: &str, expected: T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let value: T = serde_json::from_str(json).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn known_values_take_priority_over_unknown() {
+        assert_round_trip(r#""Middle School""#, Grade::MiddleSchool);
+        assert_round_trip(r#""Signature""#, EventLevel::Signature);
+        assert_round_trip(r#""league""#, EventType::League);
+        assert_round_trip(r#""finalist""#, AwardClassification::Finalist);
+        assert_round_trip(r#""division""#, AwardDesignation::Division);
+        assert_round_trip(r#""package_delivery_time""#, SkillType::PackageDeliveryTime);
+        assert_round_trip(r#""blue""#, AllianceColor::Blue);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn unknown_values_are_kept_as_sent() {
+        let unknown = "Graduate School".to_owned();
+        assert_round_trip(r#""Graduate School""#, Grade::Unknown(unknown.clone()));
+        assert_eq!(Grade::Unknown(unknown).to_string(), "Graduate School");
+
+        assert_round_trip(r#""Galactic""#, EventLevel::Unknown("Galactic".to_owned()));
+        assert_round_trip(r#""scrimmage""#, EventType::Unknown("scrimmage".to_owned()));
+        assert_round_trip(
+            r#""octofinalist""#,
+            AwardClassification::Unknown("octofinalist".to_owned()),
+        );
+        assert_round_trip(
+            r#""region""#,
+            AwardDesignation::Unknown("region".to_owned()),
+        );
+        assert_round_trip(
+            r#""autonomous""#,
+            SkillType::Unknown("autonomous".to_owned()),
+        );
+        assert_round_trip(r#""green""#, AllianceColor::Unknown("green".to_owned()));
+
+        // Known values only match exactly as the API spells them.
+        assert_round_trip(r#""Red""#, AllianceColor::Unknown("Red".to_owned()));
+        assert_round_trip(r#""high school""#, Grade::Unknown("high school".to_owned()));
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn unknown_values_must_still_be_strings() {
+        assert!(serde_json::from_str::<Grade>("42").is_err());
+        assert!(serde_json::from_str::<AllianceColor>("null").is_err());
+    }
+}
@@ -1,8 +1,20 @@
+use super::ProgramId;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Program {
-    pub id: i32,
+    pub id: ProgramId,
     pub name: String,
     pub abbr: String,
-}
\ No newline at end of file
+}
+
+impl ProgramId {
+    /// The VEX V5 Robotics Competition, formerly the VEX Robotics Competition (VRC).
+    pub const V5RC: Self = Self(1);
+
+    /// The VEX U Robotics Competition.
+    pub const VEXU: Self = Self(4);
+
+    /// The VEX IQ Robotics Competition, formerly the VEX IQ Challenge (VIQC).
+    pub const VIQRC: Self = Self(41);
+}
@@ -1,13 +1,13 @@
-use super::IdInfo;
+use super::{DivisionId, EventId, IdInfo, TeamId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Ranking {
     pub id: i32,
-    pub event: IdInfo,
-    pub division: IdInfo,
+    pub event: IdInfo<EventId>,
+    pub division: IdInfo<DivisionId>,
     pub rank: i32,
-    pub team: IdInfo,
+    pub team: IdInfo<TeamId>,
     pub wins: i32,
     pub losses: i32,
     pub ties: i32,
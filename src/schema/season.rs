@@ -1,4 +1,4 @@
-use super::IdInfo;
+use super::{IdInfo, ProgramId, SeasonId};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "client")]
@@ -8,25 +8,36 @@ use crate::client::error;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Season {
-    pub id: i32,
+    pub id: SeasonId,
     pub name: String,
-    pub program: IdInfo,
+    pub program: IdInfo<ProgramId>,
     pub start: String,
     pub end: String,
     pub years_start: i32,
     pub years_end: i32,
 }
 
+#[cfg(feature = "chrono")]
+impl Season {
+    /// Parses the season's [`start`](Self::start) timestamp.
+    pub fn start_time(&self) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+        super::parse_timestamp(&self.start)
+    }
+
+    /// Parses the season's [`end`](Self::end) timestamp.
+    pub fn end_time(&self) -> Result<chrono::DateTime<chrono::FixedOffset>, chrono::ParseError> {
+        super::parse_timestamp(&self.end)
+    }
+}
+
 #[cfg(feature = "client")]
 impl Season {
     pub async fn events(
         &self,
         client: &RobotEvents,
     ) -> Result<PaginatedResponse<Event>, error::Error> {
-        Ok(client
-            .request(format!("/seasons/{}/events", self.id))
-            .await?
-            .json()
-            .await?)
+        client
+            .request_page(format!("/seasons/{}/events", self.id))
+            .await
     }
 }
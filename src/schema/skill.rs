@@ -1,15 +1,15 @@
-use super::IdInfo;
+use super::{DivisionId, EventId, IdInfo, SeasonId, TeamId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Skill {
     pub id: i32,
-    pub event: IdInfo,
-    pub team: IdInfo,
+    pub event: IdInfo<EventId>,
+    pub team: IdInfo<TeamId>,
     #[serde(rename = "type")]
     pub skill_type: SkillType,
-    pub season: IdInfo,
-    pub division: IdInfo,
+    pub season: IdInfo<SeasonId>,
+    pub division: IdInfo<DivisionId>,
     pub rank: i32,
     pub score: i32,
     pub attempts: i32,
@@ -17,10 +17,15 @@ pub struct Skill {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum SkillType {
     Driver,
     Programming,
     PackageDeliveryTime,
+
+    /// A skills run type not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl std::fmt::Display for SkillType {
@@ -29,6 +34,7 @@ impl std::fmt::Display for SkillType {
             Self::Driver => "driver",
             Self::Programming => "programming",
             Self::PackageDeliveryTime => "package_delivery_time",
+            Self::Unknown(value) => value,
         })
     }
 }
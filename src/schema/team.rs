@@ -1,4 +1,4 @@
-use crate::schema::{IdInfo, Location};
+use crate::schema::{IdInfo, Location, ProgramId, TeamId};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "client")]
@@ -13,6 +13,7 @@ use crate::{
 use itertools::Itertools;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Grade {
     College,
 
@@ -24,6 +25,10 @@ pub enum Grade {
 
     #[serde(rename = "Elementary School")]
     ElementarySchool,
+
+    /// A grade level not known to this version of the crate, kept as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl std::fmt::Display for Grade {
@@ -33,6 +38,7 @@ impl std::fmt::Display for Grade {
             Self::HighSchool => "High School",
             Self::MiddleSchool => "Middle School",
             Self::ElementarySchool => "Elementary School",
+            Self::Unknown(value) => value,
         })
     }
 }
@@ -55,17 +61,24 @@ impl fake::Dummy<FakeGrade> for Grade {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Team {
-    pub id: i32,
+    pub id: TeamId,
     pub number: String,
     pub team_name: String,
     pub robot_name: Option<String>,
     pub organization: Option<String>,
     pub location: Location,
     pub registered: bool,
-    pub program: IdInfo,
+    pub program: IdInfo<ProgramId>,
     pub grade: Grade,
 }
 
+impl Team {
+    /// Parses the team's [`number`](Self::number) into a [`TeamNumber`].
+    pub fn team_number(&self) -> Result<TeamNumber, TeamNumberError> {
+        TeamNumber::new(&self.number)
+    }
+}
+
 #[cfg(feature = "client")]
 impl Team {
     pub async fn events(
@@ -105,6 +118,240 @@ impl Team {
     }
 }
 
+/// A team number, such as `1082R` or `BLRS2`.
+///
+/// Team numbers are normalized when parsed: surrounding and inner whitespace is removed and
+/// letters are uppercased, so `" 1082r "` and `"1082R"` are the same number. Numbers are
+/// ordered naturally, with digits compared by value, so `2A` sorts before `10A`.
+///
+/// Any non-empty alphanumeric string is accepted by [`TeamNumber::new`]. Use
+/// [`TeamNumber::parse_for`] to also check the number against a program's format.
+///
+/// # Examples
+///
+/// ```
+/// use robotevents::schema::{ProgramId, TeamNumber};
+///
+/// let mut numbers = ["10A", "2b", " 1082R"]
+///     .into_iter()
+///     .map(TeamNumber::new)
+///     .collect::<Result<Vec<_>, _>>()?;
+/// numbers.sort();
+/// assert_eq!(numbers, ["2B", "10A", "1082R"]);
+///
+/// assert!(TeamNumber::parse_for("1082R", ProgramId::V5RC).is_ok());
+/// assert!(TeamNumber::parse_for("BLRS2", ProgramId::V5RC).is_err());
+/// assert!(TeamNumber::parse_for("BLRS2", ProgramId::VEXU).is_ok());
+/// # Ok::<(), robotevents::schema::TeamNumberError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TeamNumber(String);
+
+impl TeamNumber {
+    /// Parses and normalizes a team number.
+    pub fn new(number: impl AsRef<str>) -> Result<Self, TeamNumberError> {
+        let number: String = number
+            .as_ref()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        if let Some(c) = number.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(TeamNumberError::InvalidCharacter(c));
+        }
+        if number.is_empty() {
+            return Err(TeamNumberError::Empty);
+        }
+
+        Ok(Self(number))
+    }
+
+    /// Parses a team number and checks that it is valid in the given program.
+    pub fn parse_for(number: impl AsRef<str>, program: ProgramId) -> Result<Self, TeamNumberError> {
+        let number = Self::new(number)?;
+        number.validate(program)?;
+        Ok(number)
+    }
+
+    /// Checks that the number follows the format of the given program.
+    ///
+    /// V5RC and VIQRC numbers are up to five digits followed by a letter, such as `1082R`.
+    /// VEX U numbers are two to six letters, optionally followed by up to two digits, such as
+    /// `BLRS2`. Numbers in other programs are not checked further.
+    pub fn validate(&self, program: ProgramId) -> Result<(), TeamNumberError> {
+        let digits = self.digits().len();
+        let letters = self.0[digits..]
+            .chars()
+            .take_while(char::is_ascii_alphabetic)
+            .count();
+        let rest = &self.0[digits + letters..];
+
+        let valid = match program {
+            ProgramId::V5RC | ProgramId::VIQRC => {
+                (1..=5).contains(&digits) && letters == 1 && rest.is_empty()
+            }
+            ProgramId::VEXU => {
+                digits == 0
+                    && (2..=6).contains(&letters)
+                    && rest.len() <= 2
+                    && rest.chars().all(|c| c.is_ascii_digit())
+            }
+            _ => true,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(TeamNumberError::InvalidForProgram {
+                number: self.clone(),
+                program,
+            })
+        }
+    }
+
+    /// The normalized number.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The number's leading digits, such as `1082` for `1082R`. `None` for numbers that
+    /// don't start with a digit, such as VEX U numbers.
+    pub fn numeric_part(&self) -> Option<u32> {
+        self.digits().parse().ok()
+    }
+
+    /// Everything after the number's leading digits, such as `R` for `1082R`.
+    pub fn suffix(&self) -> &str {
+        &self.0[self.digits().len()..]
+    }
+
+    fn digits(&self) -> &str {
+        let end = self
+            .0
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.0.len());
+        &self.0[..end]
+    }
+
+    /// Splits the number into alternating runs of digits and letters.
+    fn chunks(&self) -> impl Iterator<Item = &str> {
+        let mut rest = self.0.as_str();
+        std::iter::from_fn(move || {
+            let first = rest.chars().next()?;
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != first.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let (chunk, remaining) = rest.split_at(end);
+            rest = remaining;
+            Some(chunk)
+        })
+    }
+}
+
+impl Ord for TeamNumber {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let natural = self.chunks().zip(other.chunks()).find_map(|(a, b)| {
+            let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            };
+            ordering.is_ne().then_some(ordering)
+        });
+
+        natural
+            .unwrap_or_else(|| self.chunks().count().cmp(&other.chunks().count()))
+            // Break ties between numbers like `01A` and `1A` so that ordering agrees with `Eq`.
+            .then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for TeamNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::str::FromStr for TeamNumber {
+    type Err = TeamNumberError;
+
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        Self::new(number)
+    }
+}
+
+impl TryFrom<String> for TeamNumber {
+    type Error = TeamNumberError;
+
+    fn try_from(number: String) -> Result<Self, Self::Error> {
+        Self::new(number)
+    }
+}
+
+impl From<TeamNumber> for String {
+    fn from(number: TeamNumber) -> Self {
+        number.0
+    }
+}
+
+impl AsRef<str> for TeamNumber {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for TeamNumber {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for TeamNumber {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl std::fmt::Display for TeamNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An error returned when parsing a [`TeamNumber`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TeamNumberError {
+    /// The number was empty or only whitespace.
+    Empty,
+
+    /// The number contained a character other than an ASCII letter or digit.
+    InvalidCharacter(char),
+
+    /// The number doesn't follow the format of the program it was checked against.
+    InvalidForProgram {
+        number: TeamNumber,
+        program: ProgramId,
+    },
+}
+
+impl std::fmt::Display for TeamNumberError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("team number is empty"),
+            Self::InvalidCharacter(c) => write!(f, "invalid character {c:?} in team number"),
+            Self::InvalidForProgram { number, program } => {
+                write!(
+                    f,
+                    "{number} is not a valid team number in program {program}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TeamNumberError {}
+
 #[cfg(feature = "fake")]
 pub struct FakeTeam;
 
@@ -118,7 +365,7 @@ impl fake::Dummy<FakeTeam> for Team {
         };
 
         Team {
-            id: rng.gen_range(0..99999),
+            id: TeamId(rng.gen_range(0..99999)),
             number: format!("{}{}", rng.gen_range(0..99999), rng.gen_range('A'..'X')),
             team_name: Buzzword().fake_with_rng(rng),
             organization: CompanyName().fake_with_rng(rng),
@@ -126,7 +373,7 @@ impl fake::Dummy<FakeTeam> for Team {
             robot_name: Buzzword().fake_with_rng(rng),
             registered: rng.gen_bool(0.5),
             program: IdInfo {
-                id: rng.gen_range(1..60),
+                id: ProgramId(rng.gen_range(1..60)),
                 name: format!(
                     "{} {}",
                     {
@@ -151,3 +398,112 @@ impl fake::Dummy<FakeTeam> for Team {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(number: &str) -> TeamNumber {
+        TeamNumber::new(number).unwrap()
+    }
+
+    fn valid_for(number: &str, program: ProgramId) -> bool {
+        TeamNumber::parse_for(number, program).is_ok()
+    }
+
+    #[test]
+    fn v5rc_and_viqrc_numbers() {
+        for program in [ProgramId::V5RC, ProgramId::VIQRC] {
+            for valid in ["1A", "1082R", "99999Z"] {
+                assert!(valid_for(valid, program), "{valid}");
+            }
+            for invalid in ["1082", "R", "1082RR", "123456A", "1082R2", "BLRS2"] {
+                assert!(!valid_for(invalid, program), "{invalid}");
+            }
+        }
+    }
+
+    #[test]
+    fn vexu_numbers() {
+        for valid in ["BL", "BLRS", "BLRS2", "ABCDEF99"] {
+            assert!(valid_for(valid, ProgramId::VEXU), "{valid}");
+        }
+        for invalid in ["B", "ABCDEFG", "BLRS123", "1082R", "BLRS2A"] {
+            assert!(!valid_for(invalid, ProgramId::VEXU), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn other_programs_accept_any_number() {
+        assert!(valid_for("ANYTHING123", ProgramId(2)));
+    }
+
+    #[test]
+    fn program_errors_keep_the_normalized_number() {
+        assert_eq!(
+            TeamNumber::parse_for(" blrs2 ", ProgramId::V5RC),
+            Err(TeamNumberError::InvalidForProgram {
+                number: number("BLRS2"),
+                program: ProgramId::V5RC,
+            })
+        );
+    }
+
+    #[test]
+    fn numbers_are_normalized() {
+        assert_eq!(number(" 1082r "), "1082R");
+        assert_eq!(number("\t1082 R\n"), "1082R");
+        assert_eq!(number("blrs2"), number("BLRS2"));
+        assert!(valid_for(" 1082r", ProgramId::V5RC));
+    }
+
+    #[test]
+    fn invalid_numbers() {
+        assert_eq!(TeamNumber::new(""), Err(TeamNumberError::Empty));
+        assert_eq!(TeamNumber::new("   "), Err(TeamNumberError::Empty));
+        assert_eq!(
+            TeamNumber::new("1082-R"),
+            Err(TeamNumberError::InvalidCharacter('-'))
+        );
+        assert_eq!(
+            TeamNumber::new("1082É"),
+            Err(TeamNumberError::InvalidCharacter('É'))
+        );
+    }
+
+    #[test]
+    fn parts() {
+        assert_eq!(number("1082R").numeric_part(), Some(1082));
+        assert_eq!(number("1082R").suffix(), "R");
+        assert_eq!(number("BLRS2").numeric_part(), None);
+        assert_eq!(number("BLRS2").suffix(), "BLRS2");
+    }
+
+    #[test]
+    fn natural_order() {
+        let ordered = [
+            "1A", "1B", "2A", "10A", "10AB", "1082R", "99999Z", "BLRS", "BLRS2", "BLRS10",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(
+                number(pair[0]) < number(pair[1]),
+                "{} < {}",
+                pair[0],
+                pair[1]
+            );
+        }
+
+        let mut shuffled: Vec<_> = ordered.iter().rev().map(|n| number(n)).collect();
+        shuffled.sort();
+        assert_eq!(shuffled, ordered);
+    }
+
+    #[test]
+    fn leading_zeros() {
+        // Leading zeros don't change where a number sorts, but the numbers are still distinct.
+        assert!(number("01A") < number("1A"));
+        assert!(number("1A") < number("02A"));
+        assert_ne!(number("01A"), number("1A"));
+        assert_eq!(number("01A").numeric_part(), Some(1));
+    }
+}
@@ -0,0 +1,141 @@
+//! Types returned by the RobotEvents API v1.
+//!
+//! The v1 API is undocumented and used by the RobotEvents website itself. It provides some
+//! data that the v2 API doesn't, such as the world skills standings.
+//!
+//! Looking teams up by number isn't wrapped here, since the v2 API supports it and its
+//! responses are documented. Use [`RobotEvents::team_by_number`](crate::RobotEvents::team_by_number)
+//! instead.
+
+use super::TeamId;
+use serde::{Deserialize, Serialize};
+
+/// A team's position in the world skills standings for a season.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillsStanding {
+    pub rank: i32,
+    pub team: SkillsStandingTeam,
+    /// The event where the team's best combined score was set.
+    pub event: SkillsStandingEvent,
+    pub scores: SkillsStandingScores,
+    /// Whether the team is eligible to qualify for the world championship through skills.
+    #[serde(default)]
+    pub eligible: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillsStandingTeam {
+    pub id: TeamId,
+    pub program: String,
+    #[serde(rename = "team")]
+    pub number: String,
+    pub team_name: String,
+    pub organization: Option<String>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub country: Option<String>,
+    /// The team's grade level, such as `High School`.
+    pub grade_level: String,
+    /// A link to the team's page on RobotEvents.
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillsStandingEvent {
+    pub sku: String,
+    /// The date the event started on, as sent by the API.
+    pub start_date: Option<String>,
+    pub season_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillsStandingScores {
+    /// The team's best combined driver and programming score.
+    pub score: i32,
+    /// The programming score making up [`Self::score`].
+    pub programming: i32,
+    /// The driver score making up [`Self::score`].
+    pub driver: i32,
+    /// The team's highest programming score this season.
+    pub max_programming: i32,
+    /// The team's highest driver score this season.
+    pub max_driver: i32,
+    /// When the programming score was set, as sent by the API.
+    pub prog_scored_at: Option<String>,
+    /// When the driver score was set, as sent by the API.
+    pub driver_scored_at: Option<String>,
+}
+
+#[cfg(all(test, feature = "client"))]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A world skills standing as sent by the v1 API.
+    pub(crate) fn standing_json(rank: i32, team: i32, grade_level: &str) -> serde_json::Value {
+        serde_json::json!({
+            "rank": rank,
+            "team": {
+                "id": team,
+                "program": "VRC",
+                "team": format!("{team}A"),
+                "teamName": "Team",
+                "organization": "School",
+                "city": "Greenville",
+                "region": "Texas",
+                "country": "United States",
+                "gradeLevel": grade_level,
+                "link": null
+            },
+            "event": {
+                "sku": "RE-V5RC-24-1234",
+                "startDate": "2024-10-05",
+                "seasonName": "VEX V5 Robotics Competition 2024-2025: High Stakes"
+            },
+            "scores": {
+                "score": 150,
+                "programming": 60,
+                "driver": 90,
+                "maxProgramming": 65,
+                "maxDriver": 95,
+                "progScoredAt": "2024-10-05 09:00:00",
+                "driverScoredAt": "2024-10-05 10:00:00"
+            },
+            "eligible": true
+        })
+    }
+
+    #[test]
+    fn skills_standings_deserialize() {
+        let standing: SkillsStanding =
+            serde_json::from_value(standing_json(1, 7, "High School")).unwrap();
+
+        assert_eq!(standing.rank, 1);
+        assert_eq!(standing.team.id, TeamId(7));
+        assert_eq!(standing.team.number, "7A");
+        assert_eq!(standing.team.grade_level, "High School");
+        assert_eq!(standing.event.sku, "RE-V5RC-24-1234");
+        assert_eq!(
+            (standing.scores.score, standing.scores.max_driver),
+            (150, 95)
+        );
+        assert!(standing.eligible);
+
+        // Standings are serialized back to the same field names.
+        assert_eq!(
+            serde_json::to_value(&standing).unwrap(),
+            standing_json(1, 7, "High School")
+        );
+    }
+
+    #[test]
+    fn eligibility_defaults_to_false() {
+        let mut json = standing_json(1, 7, "High School");
+        json.as_object_mut().unwrap().remove("eligible");
+
+        let standing: SkillsStanding = serde_json::from_value(json).unwrap();
+        assert!(!standing.eligible);
+    }
+}